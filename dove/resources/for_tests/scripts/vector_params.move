@@ -0,0 +1,3 @@
+script {
+    fun vector_params(_a: vector<u64>, _b: vector<vector<u8>>, _c: vector<address>) {}
+}
@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use anyhow::{Error, Result};
+use better_any::{Tid, TidAble};
+use clap::Args;
+use serde_json::Value;
+
+use move_core_types::account_address::AccountAddress;
+use move_package::source_package::manifest_parser;
+use move_package::source_package::parsed_manifest::AddressDeclarations;
+
+use crate::call::fn_call::parse_signer;
+
+/// Name of the balances file in the sandbox storage directory.
+pub(crate) const BALANCES_FILE: &str = "balances.json";
+
+/// Insufficient balance of `withdraw` or `transfer`.
+pub const EINSUFFICIENT_BALANCE: u64 = 1;
+/// The balance does not fit into u128 after `deposit` or `transfer`.
+pub const EBALANCE_OVERFLOW: u64 = 2;
+
+/// PONT balances ledger: the sandbox replacement of the Substrate balances pallet.
+/// The natives read the ledger from the native context extensions, so each VM session
+/// (each `dove run` execution and each `dove test` test) works on its own copy.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Tid)]
+pub struct Balances {
+    balances: BTreeMap<AccountAddress, u128>,
+}
+
+impl Balances {
+    pub fn is_empty(&self) -> bool {
+        self.balances.is_empty()
+    }
+
+    pub fn balance(&self, addr: &AccountAddress) -> u128 {
+        self.balances.get(addr).copied().unwrap_or_default()
+    }
+
+    pub fn set_balance(&mut self, addr: AccountAddress, amount: u128) {
+        self.balances.insert(addr, amount);
+    }
+
+    /// Returns the abort code on failure.
+    pub fn deposit(&mut self, addr: AccountAddress, amount: u128) -> Result<(), u64> {
+        let balance = self
+            .balance(&addr)
+            .checked_add(amount)
+            .ok_or(EBALANCE_OVERFLOW)?;
+        self.balances.insert(addr, balance);
+        Ok(())
+    }
+
+    /// Returns the abort code on failure.
+    pub fn withdraw(&mut self, addr: AccountAddress, amount: u128) -> Result<(), u64> {
+        let balance = self
+            .balance(&addr)
+            .checked_sub(amount)
+            .ok_or(EINSUFFICIENT_BALANCE)?;
+        self.balances.insert(addr, balance);
+        Ok(())
+    }
+
+    /// Returns the abort code on failure. The ledger is not changed if the transfer fails.
+    pub fn transfer(
+        &mut self,
+        from: AccountAddress,
+        to: AccountAddress,
+        amount: u128,
+    ) -> Result<(), u64> {
+        let mut balances = self.clone();
+        balances.withdraw(from, amount)?;
+        balances.deposit(to, amount)?;
+        *self = balances;
+        Ok(())
+    }
+
+    /// Loads the balances stored in the sandbox storage directory.
+    pub fn load(storage_dir: &Path) -> Result<Option<Balances>, Error> {
+        let path = storage_dir.join(BALANCES_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let balances: BTreeMap<String, String> =
+            serde_json::from_str(&fs::read_to_string(&path)?)
+                .map_err(|err| anyhow!("Failed to parse {:?}: {}", path, err))?;
+        balances
+            .into_iter()
+            .map(|(addr, amount)| {
+                Ok((
+                    AccountAddress::from_hex_literal(&addr).map_err(|err| {
+                        anyhow!("Invalid address {} in {:?}: {}", addr, path, err)
+                    })?,
+                    amount.parse().map_err(|err| {
+                        anyhow!("Invalid balance {} in {:?}: {}", amount, path, err)
+                    })?,
+                ))
+            })
+            .collect::<Result<_>>()
+            .map(|balances| Some(Balances { balances }))
+    }
+
+    /// Stores the balances in the sandbox storage directory.
+    /// Amounts are stored as strings, as u128 does not fit into JSON numbers.
+    pub fn save(&self, storage_dir: &Path) -> Result<(), Error> {
+        let balances = self
+            .balances
+            .iter()
+            .map(|(addr, amount)| (addr.to_hex_literal(), amount.to_string()))
+            .collect::<BTreeMap<_, _>>();
+        fs::create_dir_all(storage_dir)?;
+        fs::write(
+            storage_dir.join(BALANCES_FILE),
+            serde_json::to_string_pretty(&balances)?,
+        )?;
+        Ok(())
+    }
+
+    /// Initial balances from the `[balances]` section of Move.toml:
+    /// ```toml
+    /// [balances]
+    /// Demo = 1000
+    /// "0x3" = "100000000000000000000"
+    /// ```
+    pub fn from_manifest(project_dir: &Path) -> Result<Balances, Error> {
+        let content = match fs::read_to_string(project_dir.join("Move.toml")) {
+            Ok(content) => content,
+            Err(_) => return Ok(Balances::default()),
+        };
+        let manifest = toml::from_str::<toml::Value>(&content)?;
+        let seed = match manifest.get("balances") {
+            Some(seed) => seed
+                .as_table()
+                .ok_or_else(|| anyhow!("Expected table of balances in Move.toml"))?,
+            None => return Ok(Balances::default()),
+        };
+
+        let addr_map = address_declarations(content)?;
+        let mut balances = Balances::default();
+        for (addr, amount) in seed {
+            let amount = match amount {
+                toml::Value::Integer(amount) if *amount >= 0 => *amount as u128,
+                toml::Value::String(amount) => amount
+                    .parse()
+                    .map_err(|err| anyhow!("Invalid balance {} of {}: {}", amount, addr, err))?,
+                _ => bail!("Invalid balance {} of {}", amount, addr),
+            };
+            balances.set_balance(parse_signer(addr, &addr_map)?, amount);
+        }
+        Ok(balances)
+    }
+
+    /// Amounts are strings, as u128 does not fit into JSON numbers.
+    pub fn to_json(&self) -> Value {
+        Value::Object(
+            self.balances
+                .iter()
+                .map(|(addr, amount)| (addr.to_hex_literal(), Value::String(amount.to_string())))
+                .collect(),
+        )
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = "PONT balances:\n".to_string();
+        for (addr, amount) in &self.balances {
+            text.push_str(&format!("    {}: {}\n", addr.to_hex_literal(), amount));
+        }
+        text
+    }
+}
+
+fn address_declarations(manifest: String) -> Result<AddressDeclarations, Error> {
+    let manifest = manifest_parser::parse_source_manifest(
+        manifest_parser::parse_move_manifest_string(manifest)?,
+    )?;
+    Ok(manifest.addresses.unwrap_or_default())
+}
+
+/// Balance options of `dove run` and `dove test`.
+#[derive(Args, Debug, Default, Clone)]
+pub struct BalancesArgs {
+    /// Set the PONT balance of the account: `--balance Demo=1000`.
+    /// The account is an address or an address name.
+    #[clap(long = "balance", multiple_occurrences = true)]
+    pub balances: Vec<String>,
+}
+
+impl BalancesArgs {
+    /// Overrides the balances with the options.
+    pub fn apply(
+        &self,
+        mut balances: Balances,
+        addr_map: &AddressDeclarations,
+    ) -> Result<Balances, Error> {
+        for arg in &self.balances {
+            let (addr, amount) = arg
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Expected ACCOUNT=AMOUNT balance. Actual: {}", arg))?;
+            let amount = amount
+                .trim()
+                .parse()
+                .map_err(|err| anyhow!("Invalid balance {}: {}", arg, err))?;
+            balances.set_balance(parse_signer(addr.trim(), addr_map)?, amount);
+        }
+        Ok(balances)
+    }
+
+    /// Initial balances of `dove test`: Move.toml balances and the options.
+    pub fn for_tests(&self, project_dir: &Path) -> Result<Balances, Error> {
+        let addr_map = fs::read_to_string(project_dir.join("Move.toml"))
+            .map_err(Error::new)
+            .and_then(address_declarations)
+            .unwrap_or_default();
+        self.apply(Balances::from_manifest(project_dir)?, &addr_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::account_address::AccountAddress;
+    use super::{Balances, BalancesArgs, EBALANCE_OVERFLOW, EINSUFFICIENT_BALANCE};
+
+    #[test]
+    fn test_ledger() {
+        let alice = AccountAddress::from_hex_literal("0x2").unwrap();
+        let bob = AccountAddress::from_hex_literal("0x3").unwrap();
+
+        let mut balances = BalancesArgs {
+            balances: vec!["0x2=100".to_string()],
+        }
+        .apply(Balances::default(), &Default::default())
+        .unwrap();
+        assert_eq!(balances.balance(&alice), 100);
+
+        balances.transfer(alice, bob, 30).unwrap();
+        assert_eq!(balances.balance(&alice), 70);
+        assert_eq!(balances.balance(&bob), 30);
+
+        assert_eq!(
+            balances.transfer(alice, bob, 71),
+            Err(EINSUFFICIENT_BALANCE)
+        );
+        assert_eq!(balances.balance(&alice), 70);
+
+        balances.set_balance(bob, u128::MAX);
+        assert_eq!(balances.transfer(alice, bob, 1), Err(EBALANCE_OVERFLOW));
+        assert_eq!(balances.balance(&alice), 70);
+        assert_eq!(balances.withdraw(alice, 70), Ok(()));
+        assert_eq!(balances.balance(&alice), 0);
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Error;
+use serde::Deserialize;
+use serde_json::Value;
+use move_bytecode_source_map::source_map::SourceMap;
+use move_binary_format::file_format::FunctionDefinitionIndex;
+use move_package::compilation::package_layout::CompiledPackageLayout;
+use lang::bytecode::info::{BytecodeInfo, Script};
+use crate::call::parser::ArgLiteral;
+
+/// Call arguments loaded from a JSON or TOML file.
+///
+/// JSON example:
+/// {
+///     "type_args": ["0x1::PONT::PONT"],
+///     "signers": ["_"],
+///     "args": { "amount": 100, "to": "0x1", "proofs": [[1, 2], "0304"] }
+/// }
+///
+/// `args` can also be a list of values or a map keyed by position: { "0": 100, "1": "0x1" }.
+/// String arguments are quoted as in the call: { "name": "\"hello\"" }.
+#[derive(Debug)]
+pub struct ArgsFile {
+    /// Path to the file.
+    pub path: PathBuf,
+    /// Type parameters.
+    pub type_args: Vec<String>,
+    /// Signers.
+    pub signers: Vec<String>,
+    /// Script arguments.
+    args: FileArgs,
+}
+
+#[derive(Debug)]
+enum FileArgs {
+    Positional(Vec<Value>),
+    Named(BTreeMap<String, Value>),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawArgsFile {
+    #[serde(default)]
+    type_args: Vec<String>,
+    #[serde(default)]
+    signers: Vec<String>,
+    #[serde(default)]
+    args: Option<Value>,
+}
+
+impl ArgsFile {
+    /// Loads arguments file. The format is defined by the file extension: json or toml.
+    pub fn load(path: &Path) -> Result<ArgsFile, Error> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| anyhow!("Failed to read arguments file {:?}: {}", path, err))?;
+        let ext = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+        let raw: RawArgsFile = match ext.as_str() {
+            "json" => serde_json::from_str(&content).map_err(Error::msg),
+            "toml" => toml::from_str(&content).map_err(Error::msg),
+            _ => bail!(
+                "Unsupported arguments file {:?}. json or toml extension was expected.",
+                path
+            ),
+        }
+        .map_err(|err| anyhow!("Failed to parse arguments file {:?}: {}", path, err))?;
+
+        let args = match raw.args {
+            None => FileArgs::Positional(vec![]),
+            Some(Value::Array(values)) => FileArgs::Positional(values),
+            Some(Value::Object(values)) => FileArgs::Named(values.into_iter().collect()),
+            Some(_) => bail!(
+                "Failed to parse arguments file {:?}: 'args' must be a list or a map.",
+                path
+            ),
+        };
+
+        Ok(ArgsFile {
+            path: path.to_owned(),
+            type_args: raw.type_args,
+            signers: raw.signers,
+            args,
+        })
+    }
+
+    /// Returns argument literals ordered by the function parameters.
+    /// Each literal is returned with the key it was taken from.
+    ///
+    /// `names` - names of the function parameters without signers, if known.
+    pub(crate) fn arguments(
+        &self,
+        names: Option<&[String]>,
+        params_count: usize,
+    ) -> Result<Vec<(String, ArgLiteral)>, Error> {
+        match &self.args {
+            FileArgs::Positional(values) => {
+                ensure!(
+                    values.len() == params_count,
+                    "args: The function accepts {} parameters, {} are passed",
+                    params_count,
+                    values.len()
+                );
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        let key = format!("args[{}]", index);
+                        to_literal(&key, value).map(|literal| (key, literal))
+                    })
+                    .collect()
+            }
+            FileArgs::Named(values) => {
+                let mut ordered: Vec<Option<(String, &Value)>> = vec![None; params_count];
+                for (key, value) in values {
+                    let index = match key.parse::<usize>() {
+                        Ok(index) => index,
+                        Err(_) => names
+                            .ok_or_else(|| {
+                                anyhow!(
+                                    "args.{}: Parameter names are not available for this function. \
+                                     Use parameter positions instead.",
+                                    key
+                                )
+                            })?
+                            .iter()
+                            .position(|name| name == key)
+                            .ok_or_else(|| match names {
+                                Some(names) => anyhow!(
+                                    "args.{}: Unknown parameter. Expected one of: {}",
+                                    key,
+                                    names.join(", ")
+                                ),
+                                None => anyhow!("args.{}: Unknown parameter.", key),
+                            })?,
+                    };
+                    ensure!(
+                        index < params_count,
+                        "args.{}: The function accepts {} parameters",
+                        key,
+                        params_count
+                    );
+                    if let Some((other, _)) = &ordered[index] {
+                        bail!(
+                            "args.{}: The parameter is already defined by 'args.{}'",
+                            key,
+                            other
+                        );
+                    }
+                    ordered[index] = Some((key.to_owned(), value));
+                }
+
+                ordered
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        let (key, value) = value.ok_or_else(|| {
+                            let name = names
+                                .and_then(|names| names.get(index))
+                                .cloned()
+                                .unwrap_or_else(|| index.to_string());
+                            anyhow!("args.{}: Missing parameter value.", name)
+                        })?;
+                        let key = format!("args.{}", key);
+                        to_literal(&key, value).map(|literal| (key, literal))
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Converts the file value into an argument literal.
+fn to_literal(key: &str, value: &Value) -> Result<ArgLiteral, Error> {
+    Ok(match value {
+        Value::Bool(val) => ArgLiteral::Value(val.to_string()),
+        Value::Number(val) => {
+            ensure!(
+                val.is_u64(),
+                "{}: Unsupported number {}. Use a string for big numbers.",
+                key,
+                val
+            );
+            ArgLiteral::Value(val.to_string())
+        }
+        Value::String(val) => ArgLiteral::Value(val.to_owned()),
+        Value::Array(values) => ArgLiteral::List(
+            values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| to_literal(&format!("{}[{}]", key, index), value))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Null | Value::Object(_) => bail!("{}: Unsupported value {}", key, value),
+    })
+}
+
+/// Returns the parameter names of the function from the package source map.
+pub(crate) fn parameter_names(bi: &BytecodeInfo, script: &Script) -> Option<Vec<String>> {
+    let path = PathBuf::from(&bi.bytecode_ref().0);
+    let name = path.file_stem()?;
+    let mut source_map = path
+        .parent()?
+        .parent()?
+        .join(CompiledPackageLayout::SourceMaps.path())
+        .join(name);
+    source_map.set_extension("mvsm");
+    let source_map: SourceMap = bcs::from_bytes(&fs::read(source_map).ok()?).ok()?;
+
+    let function = source_map
+        .get_function_source_map(FunctionDefinitionIndex(script.index))
+        .ok()?;
+    Some(
+        function
+            .parameters
+            .iter()
+            .map(|(name, _)| name.to_owned())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use crate::call::args_file::ArgsFile;
+    use crate::call::parser::ArgLiteral;
+
+    fn v(val: &str) -> ArgLiteral {
+        ArgLiteral::Value(val.to_owned())
+    }
+
+    fn load(extension: &str, content: &str) -> ArgsFile {
+        let file = tempfile::Builder::new()
+            .prefix("dove_args_file")
+            .suffix(&format!(".{}", extension))
+            .tempfile()
+            .unwrap();
+        fs::write(file.path(), content).unwrap();
+        ArgsFile::load(file.path()).unwrap()
+    }
+
+    #[test]
+    fn test_json_args_file() {
+        let file = load(
+            "json",
+            r#"{
+                "type_args": ["0x1::PONT::PONT"],
+                "signers": ["_"],
+                "args": { "amount": 100, "1": "0x1", "proofs": [[1, 2], "0304"] }
+            }"#,
+        );
+        assert_eq!(file.type_args, vec!["0x1::PONT::PONT".to_owned()]);
+        assert_eq!(file.signers, vec!["_".to_owned()]);
+
+        let names = ["amount".to_owned(), "to".to_owned(), "proofs".to_owned()];
+        let args = file.arguments(Some(&names), 3).unwrap();
+        assert_eq!(
+            args,
+            vec![
+                ("args.amount".to_owned(), v("100")),
+                ("args.1".to_owned(), v("0x1")),
+                (
+                    "args.proofs".to_owned(),
+                    ArgLiteral::List(vec![ArgLiteral::List(vec![v("1"), v("2")]), v("0304")])
+                ),
+            ]
+        );
+
+        let err = file.arguments(None, 3).unwrap_err().to_string();
+        assert!(err.starts_with("args.amount:"), "{}", err);
+        let err = file
+            .arguments(Some(&["a".to_owned(), "b".to_owned()]), 2)
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("args.amount: Unknown parameter"), "{}", err);
+    }
+
+    #[test]
+    fn test_toml_args_file() {
+        let file = load(
+            "toml",
+            r#"
+            type_args = ["u8"]
+            args = [true, "340282366920938463463374607431768211456", [1, 2]]
+            "#,
+        );
+        assert_eq!(file.type_args, vec!["u8".to_owned()]);
+        assert!(file.signers.is_empty());
+        assert_eq!(
+            file.arguments(None, 3).unwrap(),
+            vec![
+                ("args[0]".to_owned(), v("true")),
+                (
+                    "args[1]".to_owned(),
+                    v("340282366920938463463374607431768211456")
+                ),
+                ("args[2]".to_owned(), ArgLiteral::List(vec![v("1"), v("2")])),
+            ]
+        );
+        file.arguments(None, 2).unwrap_err();
+
+        let file = load(
+            "toml",
+            r#"
+            args = [[1, -2]]
+            "#,
+        );
+        let err = file.arguments(None, 1).unwrap_err().to_string();
+        assert!(err.starts_with("args[0][1]:"), "{}", err);
+    }
+}
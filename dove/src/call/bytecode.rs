@@ -1,10 +1,16 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use anyhow::Error;
 use move_binary_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
 use move_binary_format::file_format::{CompiledScript, empty_module};
 use move_package::compilation::package_layout::CompiledPackageLayout;
 use lang::bytecode::accessor::{Bytecode, BytecodeAccess, BytecodeRef, BytecodeType};
+use net::{Block, Net};
 use crate::context::Context;
 
 /// Dove bytecode resolver.
@@ -148,3 +154,113 @@ impl BytecodeAccess for DoveBytecode {
         }))
     }
 }
+
+/// Bytecode resolver backed by the chain.
+/// Only modules of the given address are available.
+pub struct NetBytecode {
+    net: Box<dyn Net>,
+    address: AccountAddress,
+    height: Option<Block>,
+    loaded: RefCell<HashMap<String, Vec<u8>>>,
+}
+
+impl NetBytecode {
+    /// Creates a new [NetBytecode].
+    pub fn new(net: Box<dyn Net>, address: AccountAddress, height: Option<Block>) -> NetBytecode {
+        NetBytecode {
+            net,
+            address,
+            height,
+            loaded: Default::default(),
+        }
+    }
+}
+
+impl BytecodeAccess for NetBytecode {
+    fn list<'a>(
+        &self,
+        _package: Option<&'a str>,
+        name: Option<&'a str>,
+        tp: Option<BytecodeType>,
+    ) -> Result<Vec<BytecodeRef>, Error> {
+        if let Some(BytecodeType::Script) = tp {
+            return Ok(vec![]);
+        }
+        let name = match name {
+            Some(name) => name,
+            None => bail!("The module name is required to load the module from the chain."),
+        };
+
+        let module_id = ModuleId::new(self.address, Identifier::new(name)?);
+        let module = self
+            .net
+            .get_module(&module_id, &self.height)
+            .map_err(|err| {
+                anyhow!(
+                    "Failed to load module {} from the chain: {}",
+                    module_id,
+                    err
+                )
+            })?;
+        Ok(match module {
+            Some(bytes) => {
+                let rf = module_id.to_string();
+                self.loaded.borrow_mut().insert(rf.clone(), bytes.0);
+                vec![BytecodeRef(rf, BytecodeType::Module)]
+            }
+            None => vec![],
+        })
+    }
+
+    fn load(&self, rf: BytecodeRef) -> Result<Option<Bytecode>, Error> {
+        let bytecode = match self.loaded.borrow_mut().remove(&rf.0) {
+            Some(bytecode) => bytecode,
+            None => return Ok(None),
+        };
+        Ok(Some(match rf.1 {
+            BytecodeType::Script => bail!("Scripts can't be loaded from the chain."),
+            BytecodeType::Module => Bytecode::Module(CompiledModule::deserialize(&bytecode)?, rf),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use move_binary_format::file_format::empty_module;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::{ModuleId, CORE_CODE_ADDRESS};
+    use lang::bytecode::accessor::{Bytecode, BytecodeAccess, BytecodeType};
+    use net::test_utils::FakeNet;
+
+    use super::NetBytecode;
+
+    #[test]
+    fn test_net_bytecode() {
+        let mut bytes = vec![];
+        empty_module().serialize(&mut bytes).unwrap();
+        let module_id = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("Store").unwrap());
+        let mut net = FakeNet::default();
+        net.modules.insert(module_id.clone(), bytes);
+        let bytecode = NetBytecode::new(Box::new(net), CORE_CODE_ADDRESS, None);
+
+        let mut refs = bytecode.list(None, Some("Store"), None).unwrap();
+        assert_eq!(refs.len(), 1);
+        let rf = refs.remove(0);
+        assert_eq!(rf.0, module_id.to_string());
+        assert!(matches!(
+            bytecode.load(rf).unwrap(),
+            Some(Bytecode::Module(..))
+        ));
+
+        // scripts are not stored on the chain
+        assert!(bytecode
+            .list(None, Some("Store"), Some(BytecodeType::Script))
+            .unwrap()
+            .is_empty());
+        assert!(bytecode
+            .list(None, Some("Missing"), None)
+            .unwrap()
+            .is_empty());
+        bytecode.list(None, None, None).unwrap_err();
+    }
+}
@@ -3,7 +3,9 @@ use clap::Parser;
 use std::fmt::Debug;
 use std::convert::TryFrom;
 use std::mem;
+use std::path::PathBuf;
 use move_package::source_package::parsed_manifest::AddressDeclarations;
+use crate::call::args_file::ArgsFile;
 use crate::call::parser::{parse_call, Call, parse_tp_param};
 
 #[derive(Parser, Debug)]
@@ -16,7 +18,7 @@ Examples:
       'ALIAS_ADDRESSES::Module::function()'
       '0x1::Module::function' --args [10,10] true ALIAS_ADDRESSES 100 0x1 --type 0x01::Dfinance::USD
       "#)]
-    call: String,
+    pub(crate) call: String,
     #[clap(
         help = r#"Script type parameters, e.g. 0x1::Dfinance::USD"#,
         name = "Script type parameters.",
@@ -24,7 +26,7 @@ Examples:
         short = 't',
         multiple_values = true
     )]
-    type_parameters: Option<Vec<String>>,
+    pub(crate) type_parameters: Option<Vec<String>>,
     #[clap(
         help = r#"Script arguments, e.g. 10 20 30"#,
         name = "Script arguments.",
@@ -32,17 +34,37 @@ Examples:
         short = 'a',
         multiple_values = true
     )]
-    params: Option<Vec<String>>,
+    pub(crate) params: Option<Vec<String>>,
+    #[clap(
+        help = r#"Path to a JSON or TOML file with type parameters, signers and script arguments.
+Arguments may be passed as a list or as a map keyed by parameter names, e.g.
+      {"type_args": ["0x1::PONT::PONT"], "signers": ["_"], "args": {"amount": 100, "to": "0x1"}}"#,
+        name = "Script arguments file.",
+        long = "args-file",
+        short = 'f'
+    )]
+    pub(crate) args_file: Option<PathBuf>,
     #[clap(
         help = r#"Move package name"#,
         name = "Move package name.",
         long = "package",
         short = 'c'
     )]
-    package: Option<String>,
+    pub(crate) package: Option<String>,
 }
 
 impl CallDeclarationCmd {
+    /// Creates call declaration without separate type parameters and arguments.
+    pub fn new(call: String, package: Option<String>) -> Self {
+        Self {
+            call,
+            type_parameters: None,
+            params: None,
+            args_file: None,
+            package,
+        }
+    }
+
     /// Takes call data.
     #[must_use]
     pub fn take(&mut self) -> Self {
@@ -50,6 +72,7 @@ impl CallDeclarationCmd {
             call: mem::take(&mut self.call),
             type_parameters: self.type_parameters.take(),
             params: self.params.take(),
+            args_file: self.args_file.take(),
             package: self.package.take(),
         }
     }
@@ -74,9 +97,40 @@ impl TryFrom<(&AddressDeclarations, CallDeclarationCmd)> for CallDeclaration {
             );
         }
 
+        let args_file = match cmd.args_file {
+            Some(path) => {
+                let file = ArgsFile::load(&path)?;
+                ensure!(
+                    call.args().is_empty(),
+                    "Script arguments are passed both in the call and in the arguments file {:?}.",
+                    path
+                );
+                if !file.type_args.is_empty() {
+                    ensure!(
+                        call.tp_params().is_empty(),
+                        "Type parameters are passed both in the call and in the arguments file {:?}.",
+                        path
+                    );
+                    call.set_tp_params(
+                        file.type_args
+                            .iter()
+                            .enumerate()
+                            .map(|(index, tp)| {
+                                parse_tp_param(addr_map, tp)
+                                    .map_err(|err| anyhow!("type_args[{}]: {}", index, err))
+                            })
+                            .collect::<Result<_, _>>()?,
+                    );
+                }
+                Some(file)
+            }
+            None => None,
+        };
+
         Ok(CallDeclaration {
             call,
             package: cmd.package,
+            args_file,
         })
     }
 }
@@ -87,4 +141,6 @@ pub struct CallDeclaration {
     pub call: Call,
     /// Package
     pub package: Option<String>,
+    /// Arguments file.
+    pub args_file: Option<ArgsFile>,
 }
@@ -6,25 +6,36 @@ use move_core_types::account_address::AccountAddress;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{CORE_CODE_ADDRESS, TypeTag};
 use move_package::source_package::parsed_manifest::AddressDeclarations;
+use url::Url;
 use lang::bytecode::accessor::BytecodeType;
 use lang::bytecode::{find, SearchParams};
-use lang::bytecode::info::{BytecodeInfo, Type};
+use lang::bytecode::info::{BytecodeInfo, Script, Type};
+use net::make_net;
 use crate::context::Context;
-use crate::call::model::{Signer, ScriptArg, Transaction, Signers, EnrichedTransaction, Call};
-use crate::call::parser::parse_vec;
-use crate::call::bytecode::DoveBytecode;
-
-fn diem_root_address() -> AccountAddress {
+use crate::call::model::{
+    Signer, ScriptArg, Transaction, Signers, EnrichedTransaction, Call, TxMetadata, encode_args,
+};
+use crate::call::args_file::{ArgsFile, parameter_names};
+use crate::call::parser::{parse_literal, parse_string_literal, ArgLiteral};
+use crate::call::bytecode::{DoveBytecode, NetBytecode};
+use crate::natives::U256;
+
+pub(crate) fn diem_root_address() -> AccountAddress {
     AccountAddress::from_hex_literal("0xA550C18")
         .expect("Parsing valid hex literal should always succeed")
 }
 
 /// Transaction config.
+#[derive(Clone)]
 pub struct Config {
     /// Is transaction for chain execution.
     tx_context: bool,
     /// Prohibit the definition of signers.
     deny_signers_definition: bool,
+    /// Transaction metadata. If set, the transaction is created in version 2.
+    metadata: Option<TxMetadata>,
+    /// Node API url. If set, modules missing in the project are loaded from the chain.
+    chain_url: Option<Url>,
 }
 
 impl Config {
@@ -33,6 +44,8 @@ impl Config {
         Config {
             tx_context: true,
             deny_signers_definition: true,
+            metadata: None,
+            chain_url: None,
         }
     }
 
@@ -41,56 +54,73 @@ impl Config {
         Config {
             tx_context: false,
             deny_signers_definition: false,
+            metadata: None,
+            chain_url: None,
+        }
+    }
+
+    /// Sets transaction metadata: chain id, expiration block and memo.
+    pub fn with_metadata(mut self, metadata: TxMetadata) -> Config {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Sets the node API url to load the modules missing in the project.
+    pub fn with_chain_url(mut self, url: Option<Url>) -> Config {
+        self.chain_url = url;
+        self
+    }
+
+    /// Applies the config to the created transaction.
+    fn finalize(&self, tx: Transaction) -> Transaction {
+        match &self.metadata {
+            Some(metadata) => tx.with_metadata(metadata.clone()),
+            None => tx,
         }
     }
 }
 
+/// Call arguments.
+pub(crate) enum CallArgs {
+    /// Arguments passed in the command line.
+    Line(Vec<String>),
+    /// Arguments passed in the arguments file.
+    File(ArgsFile),
+}
+
 pub(crate) fn make_script_call(
     ctx: &Context,
     addr_map: &AddressDeclarations,
     name: Identifier,
     type_tag: Vec<TypeTag>,
-    args: Vec<String>,
+    args: CallArgs,
     package_name: Option<String>,
     cfg: Config,
 ) -> Result<EnrichedTransaction, Error> {
-    let access = DoveBytecode::new(ctx);
-    let functions = find(
-        access,
-        SearchParams {
-            tp: Some(BytecodeType::Script),
-            package: package_name.as_deref(),
-            name: Some(name.as_str()),
-        },
-    )?
-    .filter_map(|f| f.ok());
-    let (signers, args, info) =
-        select_function(functions, &name, &args, &type_tag, &cfg, addr_map)?;
+    let functions = find_scripts(ctx, &name, package_name.as_deref())?;
+    let (signers, args, info) = select_function(
+        functions.into_iter(),
+        &name,
+        &args,
+        &type_tag,
+        &cfg,
+        addr_map,
+    )?;
+
+    let mut code = Vec::new();
+    info.serialize(&mut code)?;
 
     Ok(if cfg.tx_context {
-        let (_, mut tx) = match signers {
-            Signers::Explicit(signers) => (
-                signers,
-                Transaction::new_script_tx(vec![], vec![], args, type_tag)?,
-            ),
-            Signers::Implicit(signers) => (
-                vec![],
-                Transaction::new_script_tx(signers, vec![], args, type_tag)?,
-            ),
+        let tx = match signers {
+            Signers::Explicit(_) => Transaction::new_script_tx(vec![], code, args, type_tag)?,
+            Signers::Implicit(signers) => {
+                Transaction::new_script_tx(signers, code, args, type_tag)?
+            }
         };
 
-        let mut buff = Vec::new();
-        info.serialize(&mut buff)?;
-
-        match &mut tx.inner_mut().call {
-            Call::Script { code, .. } => *code = buff,
-            Call::ScriptFunction { .. } => {
-                // no-op
-            }
-        }
         EnrichedTransaction::Global {
             bi: info,
-            tx,
+            tx: cfg.finalize(tx),
             name: name.into_string(),
         }
     } else {
@@ -101,10 +131,10 @@ pub(crate) fn make_script_call(
 
         EnrichedTransaction::Local {
             bi: info,
-            args,
+            call: Call::Script { code },
+            args: encode_args(&args)?,
             signers,
             type_tag,
-            func_name: None,
         }
     })
 }
@@ -117,30 +147,18 @@ pub(crate) fn make_function_call(
     module: Identifier,
     func: Identifier,
     type_tag: Vec<TypeTag>,
-    args: Vec<String>,
+    args: CallArgs,
     package_name: Option<String>,
     cfg: Config,
 ) -> Result<EnrichedTransaction, Error> {
-    let access = DoveBytecode::new(ctx);
-    let modules = find(
-        access,
-        SearchParams {
-            tp: Some(BytecodeType::Module),
-            package: package_name.as_deref(),
-            name: Some(module.as_str()),
-        },
-    )?
-    .filter_map(|info| info.ok())
-    .filter(|info| {
-        if address.is_some() {
-            info.address() == address
-        } else {
-            true
+    let mut modules = find_modules(ctx, address, &module, package_name.as_deref())?;
+    if modules.is_empty() {
+        if let (Some(url), Some(address)) = (&cfg.chain_url, address) {
+            modules = find_chain_modules(url, address, &module)?;
         }
-    })
-    .filter(|info| info.name() == module.as_str());
+    }
     let (signers, args, info) =
-        select_function(modules, &func, &args, &type_tag, &cfg, addr_map)?;
+        select_function(modules.into_iter(), &func, &args, &type_tag, &cfg, addr_map)?;
 
     let addr = info.address().unwrap_or(CORE_CODE_ADDRESS);
     let tx_name = format!("{}_{}", module, func);
@@ -156,7 +174,7 @@ pub(crate) fn make_function_call(
         };
         Ok(EnrichedTransaction::Global {
             bi: info,
-            tx,
+            tx: cfg.finalize(tx),
             name: tx_name,
         })
     } else {
@@ -167,18 +185,87 @@ pub(crate) fn make_function_call(
 
         Ok(EnrichedTransaction::Local {
             bi: info,
+            call: Call::ScriptFunction {
+                mod_address: addr,
+                mod_name: module,
+                func_name: func,
+            },
+            args: encode_args(&args)?,
             signers,
-            args,
             type_tag,
-            func_name: Some(func.into_string()),
         })
     }
 }
 
+/// Returns the scripts with the given name.
+pub(crate) fn find_scripts(
+    ctx: &Context,
+    name: &Identifier,
+    package_name: Option<&str>,
+) -> Result<Vec<BytecodeInfo>, Error> {
+    let access = DoveBytecode::new(ctx);
+    Ok(find(
+        access,
+        SearchParams {
+            tp: Some(BytecodeType::Script),
+            package: package_name,
+            name: Some(name.as_str()),
+        },
+    )?
+    .filter_map(|f| f.ok())
+    .collect())
+}
+
+/// Returns the modules with the given name and address.
+pub(crate) fn find_modules(
+    ctx: &Context,
+    address: Option<AccountAddress>,
+    module: &Identifier,
+    package_name: Option<&str>,
+) -> Result<Vec<BytecodeInfo>, Error> {
+    let access = DoveBytecode::new(ctx);
+    Ok(find(
+        access,
+        SearchParams {
+            tp: Some(BytecodeType::Module),
+            package: package_name,
+            name: Some(module.as_str()),
+        },
+    )?
+    .filter_map(|info| info.ok())
+    .filter(|info| {
+        if address.is_some() {
+            info.address() == address
+        } else {
+            true
+        }
+    })
+    .filter(|info| info.name() == module.as_str())
+    .collect())
+}
+
+/// Loads the module from the chain.
+pub(crate) fn find_chain_modules(
+    url: &Url,
+    address: AccountAddress,
+    module: &Identifier,
+) -> Result<Vec<BytecodeInfo>, Error> {
+    let access = NetBytecode::new(make_net(url.clone())?, address, None);
+    find(
+        access,
+        SearchParams {
+            tp: Some(BytecodeType::Module),
+            package: None,
+            name: Some(module.as_str()),
+        },
+    )?
+    .collect()
+}
+
 fn select_function<I>(
     info_iter: I,
     name: &Identifier,
-    args: &[String],
+    args: &CallArgs,
     type_tag: &[TypeTag],
     cfg: &Config,
     addr_map: &AddressDeclarations,
@@ -190,12 +277,21 @@ where
         .filter_map(|info| info.find_script_function(name.as_str()).map(|f| (info, f)))
         .filter(|(_, f)| type_tag.len() == f.type_params_count())
         .map(|(i, script)| {
-            prepare_function_signature(
-                &script.parameters,
-                args,
-                !cfg.deny_signers_definition,
-                addr_map,
-            )
+            match args {
+                CallArgs::Line(args) => prepare_function_signature(
+                    &script.parameters,
+                    args,
+                    !cfg.deny_signers_definition,
+                    addr_map,
+                ),
+                CallArgs::File(file) => prepare_file_signature(
+                    &i,
+                    &script,
+                    file,
+                    !cfg.deny_signers_definition,
+                    addr_map,
+                ),
+            }
             .map(|(signers, args)| (i, script, signers, args))
         })
         .collect::<Vec<Result<_, _>>>();
@@ -249,38 +345,8 @@ fn prepare_function_signature(
         .collect::<Result<Vec<_>, Error>>()?;
 
     if use_explicit_signers {
-        let signers = call_args[..args_index]
-            .iter()
-            .map(|arg| {
-                if arg.starts_with("0x") {
-                    AccountAddress::from_hex_literal(arg)
-                        .map_err(|err| anyhow!("Failed to parse signer:{}", err))
-                } else {
-                    Signer::from_str(arg).and_then(|s| {
-                        Ok(match s {
-                            Signer::Root => diem_root_address(),
-                            Signer::Placeholder => {
-                                return Err(anyhow!(
-                                    "Use explicit signer instead of placeholder"
-                                ));
-                            }
-                            Signer::Name(name) => {
-                                addr_map.get(&name).and_then(|addr| *addr).ok_or_else(|| {
-                                    anyhow!("Failed to find address with name:{}", arg)
-                                })?
-                            }
-                        })
-                    })
-                }
-            })
-            .collect::<Result<Vec<AccountAddress>, Error>>()
-            .map_err(|err| anyhow!("Failed to parse signer:{}", err))?;
-        ensure!(
-            signers.len() == signers_count,
-            "The function accepts {} signers, {} are passed",
-            signers_count,
-            signers.len()
-        );
+        let signers =
+            prepare_explicit_signers(&call_args[..args_index], signers_count, addr_map)?;
         Ok((Signers::Explicit(signers), params))
     } else {
         let mut signers = (0..signers_count)
@@ -299,11 +365,133 @@ fn prepare_function_signature(
     }
 }
 
-fn prepare_arg(
+fn prepare_file_signature(
+    info: &BytecodeInfo,
+    script: &Script,
+    file: &ArgsFile,
+    use_explicit_signers: bool,
+    addr_map: &AddressDeclarations,
+) -> Result<(Signers, Vec<ScriptArg>), Error> {
+    let signers_count = script
+        .parameters
+        .iter()
+        .take_while(|tp| **tp == Type::Signer)
+        .count();
+    let names = parameter_names(info, script)
+        .filter(|names| names.len() == script.parameters.len())
+        .map(|names| names[signers_count..].to_vec());
+
+    let params = script.parameters[signers_count..]
+        .iter()
+        .zip(file.arguments(names.as_deref(), script.parameters.len() - signers_count)?)
+        .map(|(tp, (key, literal))| {
+            prepare_value(tp, literal, addr_map).map_err(|err| anyhow!("{}: {}", key, err))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    if use_explicit_signers {
+        let signers = prepare_explicit_signers(&file.signers, signers_count, addr_map)?;
+        Ok((Signers::Explicit(signers), params))
+    } else {
+        ensure!(
+            file.signers.len() <= signers_count,
+            "signers: The function accepts {} signers, {} are passed",
+            signers_count,
+            file.signers.len()
+        );
+        let mut signers = file
+            .signers
+            .iter()
+            .enumerate()
+            .map(|(index, arg)| {
+                Signer::from_str(arg).map_err(|err| anyhow!("signers[{}]: {}", index, err))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        for _ in signers.len()..signers_count {
+            signers.push(Signer::Placeholder);
+        }
+
+        Ok((Signers::Implicit(signers), params))
+    }
+}
+
+/// Parses the explicit signer: address, `root` or named address.
+pub(crate) fn parse_signer(
+    arg: &str,
+    addr_map: &AddressDeclarations,
+) -> Result<AccountAddress, Error> {
+    if arg.starts_with("0x") {
+        AccountAddress::from_hex_literal(arg)
+            .map_err(|err| anyhow!("Failed to parse signer:{}", err))
+    } else {
+        Signer::from_str(arg).and_then(|s| {
+            Ok(match s {
+                Signer::Root => diem_root_address(),
+                Signer::Placeholder => {
+                    return Err(anyhow!("Use explicit signer instead of placeholder"));
+                }
+                Signer::Name(name) => addr_map
+                    .get(&name)
+                    .and_then(|addr| *addr)
+                    .ok_or_else(|| anyhow!("Failed to find address with name:{}", arg))?,
+            })
+        })
+    }
+}
+
+fn prepare_explicit_signers(
+    signers: &[String],
+    signers_count: usize,
+    addr_map: &AddressDeclarations,
+) -> Result<Vec<AccountAddress>, Error> {
+    let signers = signers
+        .iter()
+        .map(|arg| parse_signer(arg, addr_map))
+        .collect::<Result<Vec<AccountAddress>, Error>>()?;
+    ensure!(
+        signers.len() == signers_count,
+        "The function accepts {} signers, {} are passed",
+        signers_count,
+        signers.len()
+    );
+    Ok(signers)
+}
+
+pub(crate) fn prepare_arg(
     arg_type: &Type,
     arg_value: &str,
     addr_map: &AddressDeclarations,
 ) -> Result<ScriptArg, Error> {
+    let literal = parse_literal(arg_value).map_err(|err| parse_err(arg_type, arg_value, err))?;
+    prepare_value(arg_type, literal, addr_map)
+}
+
+fn prepare_value(
+    arg_type: &Type,
+    literal: ArgLiteral,
+    addr_map: &AddressDeclarations,
+) -> Result<ScriptArg, Error> {
+    let arg_value = match literal {
+        ArgLiteral::Value(value) => value,
+        ArgLiteral::List(elements) => {
+            return if let Type::Vector(tp) = arg_type {
+                Ok(ScriptArg::Vector(
+                    elements
+                        .into_iter()
+                        .map(|element| prepare_value(tp, element, addr_map))
+                        .collect::<Result<_, Error>>()?,
+                ))
+            } else {
+                Err(anyhow!(
+                    "Parameter has type {:?}. Vector is not expected.",
+                    arg_type
+                ))
+            };
+        }
+    };
+    let arg_value = arg_value.as_str();
+
     macro_rules! parse_primitive {
         ($script_arg:expr) => {
             $script_arg(
@@ -319,39 +507,37 @@ fn prepare_arg(
         Type::U8 => parse_primitive!(ScriptArg::U8),
         Type::U64 => parse_primitive!(ScriptArg::U64),
         Type::U128 => parse_primitive!(ScriptArg::U128),
+        Type::U256 => ScriptArg::U256(
+            parse_u256(arg_value).map_err(|err| parse_err(arg_type, arg_value, err))?,
+        ),
         Type::Address => ScriptArg::Address(parse_address(arg_value, addr_map)?),
+        Type::String => {
+            let bytes = parse_string_literal(arg_value)
+                .map_err(|err| parse_err(arg_type, arg_value, err))?;
+            std::str::from_utf8(&bytes).map_err(|err| parse_err(arg_type, arg_value, err))?;
+            ScriptArg::vector_u8(bytes)
+        }
+        Type::AsciiString => {
+            let bytes = parse_string_literal(arg_value)
+                .map_err(|err| parse_err(arg_type, arg_value, err))?;
+            ensure!(
+                bytes.is_ascii(),
+                "Parameter has type {:?}. Failed to parse {}. Non-ASCII characters found.",
+                arg_type,
+                arg_value
+            );
+            ScriptArg::vector_u8(bytes)
+        }
         Type::Vector(tp) => match tp.as_ref() {
-            Type::Bool => ScriptArg::VectorBool(
-                parse_vec(arg_value, "bool")
-                    .map_err(|err| parse_err(arg_type, arg_value, err))?,
-            ),
-            Type::U8 => ScriptArg::VectorU8(if arg_value.contains('[') {
-                parse_vec(arg_value, "u8").map_err(|err| parse_err(arg_type, arg_value, err))?
-            } else {
-                hex::decode(arg_value).map_err(|err| parse_err(arg_type, arg_value, err))?
-            }),
-            Type::U64 => ScriptArg::VectorU64(
-                parse_vec(arg_value, "u64").map_err(|err| parse_err(arg_type, arg_value, err))?,
+            // vector<u8> may be passed as a hex string.
+            Type::U8 => ScriptArg::vector_u8(
+                hex::decode(arg_value).map_err(|err| parse_err(arg_type, arg_value, err))?,
             ),
-            Type::U128 => ScriptArg::VectorU128(
-                parse_vec(arg_value, "u64").map_err(|err| parse_err(arg_type, arg_value, err))?,
+            _ => anyhow::bail!(
+                "Parameter has type {:?}. Vector in format [n1, n2, ..., nn] is expected. Actual:'{}'",
+                arg_type,
+                arg_value
             ),
-            Type::Address => {
-                let addresses = parse_vec::<String>(arg_value, "vector<address>")
-                    .map_err(|err| parse_err(arg_type, arg_value, err))?
-                    .into_iter()
-                    .map(|addr| parse_address(&addr, addr_map))
-                    .collect::<Result<Vec<_>, Error>>()?;
-                ScriptArg::VectorAddress(addresses)
-            }
-            Type::Signer
-            | Type::Vector(_)
-            | Type::Struct(_)
-            | Type::Reference(_)
-            | Type::MutableReference(_)
-            | Type::TypeParameter(_) => {
-                anyhow::bail!("Unexpected script parameter: {:?}", arg_type)
-            }
         },
         Type::Signer
         | Type::Struct(_)
@@ -398,6 +584,15 @@ fn parse_address(
     }
 }
 
+/// Parses u256 from a decimal or `0x`-prefixed hex string.
+fn parse_u256(value: &str) -> Result<U256, Error> {
+    if let Some(hex) = value.strip_prefix("0x") {
+        U256::from_str_radix(hex, 16).map_err(|err| anyhow!("{:?}", err))
+    } else {
+        U256::from_dec_str(value).map_err(|err| anyhow!("{:?}", err))
+    }
+}
+
 fn parse_err<D: Debug>(tp: &Type, value: &str, err: D) -> Error {
     anyhow!(
         "Parameter has type {:?}. Failed to parse {}. Error:'{:?}'",
@@ -414,6 +609,7 @@ mod call_tests {
     use lang::bytecode::info::Type;
     use crate::call::model::ScriptArg;
     use crate::call::fn_call::prepare_function_signature;
+    use crate::natives::U256;
 
     fn s(v: &str) -> String {
         v.to_string()
@@ -496,14 +692,196 @@ mod call_tests {
         assert_eq!(
             args,
             vec![
-                ScriptArg::VectorBool(vec![true, false]),
-                ScriptArg::VectorU8(vec![100]),
-                ScriptArg::VectorU8(vec![]),
-                ScriptArg::VectorU8(vec![1, 2]),
-                ScriptArg::VectorU64(vec![1000, 0]),
-                ScriptArg::VectorU128(vec![0]),
-                ScriptArg::VectorAddress(vec![addr("0x1"), addr("0x2")]),
+                ScriptArg::Vector(vec![ScriptArg::Bool(true), ScriptArg::Bool(false)]),
+                ScriptArg::vector_u8(vec![100]),
+                ScriptArg::vector_u8(vec![]),
+                ScriptArg::vector_u8(vec![1, 2]),
+                ScriptArg::Vector(vec![ScriptArg::U64(1000), ScriptArg::U64(0)]),
+                ScriptArg::Vector(vec![ScriptArg::U128(0)]),
+                ScriptArg::Vector(vec![
+                    ScriptArg::Address(addr("0x1")),
+                    ScriptArg::Address(addr("0x2")),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_args_types() {
+        let vec_of = |tp: Type| Type::Vector(Box::new(tp));
+
+        let (_, args) = prepare_function_signature(
+            &[
+                vec_of(vec_of(Type::U8)),
+                vec_of(vec_of(Type::Address)),
+                vec_of(vec_of(vec_of(Type::U64))),
+            ],
+            &[
+                s("[0102, [3, 4], []]"),
+                s("[[0x1], [0x1, 0x2]]"),
+                s("[[[1], []], []]"),
+            ],
+            true,
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                ScriptArg::Vector(vec![
+                    ScriptArg::vector_u8(vec![1, 2]),
+                    ScriptArg::vector_u8(vec![3, 4]),
+                    ScriptArg::vector_u8(vec![]),
+                ]),
+                ScriptArg::Vector(vec![
+                    ScriptArg::Vector(vec![ScriptArg::Address(addr("0x1"))]),
+                    ScriptArg::Vector(vec![
+                        ScriptArg::Address(addr("0x1")),
+                        ScriptArg::Address(addr("0x2")),
+                    ]),
+                ]),
+                ScriptArg::Vector(vec![
+                    ScriptArg::Vector(vec![
+                        ScriptArg::Vector(vec![ScriptArg::U64(1)]),
+                        ScriptArg::Vector(vec![]),
+                    ]),
+                    ScriptArg::Vector(vec![]),
+                ]),
+            ]
+        );
+        assert_eq!(
+            bcs::to_bytes(&args[0]).unwrap(),
+            bcs::to_bytes(&vec![vec![1u8, 2], vec![3, 4], vec![]]).unwrap()
+        );
+
+        prepare_function_signature(
+            &[vec_of(vec_of(Type::U8))],
+            &[s("[[1, 2], 3]")],
+            true,
+            &Default::default(),
+        )
+        .unwrap_err();
+        prepare_function_signature(&[Type::U64], &[s("[1]")], true, &Default::default())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_decode_args() {
+        let vec_of = |tp: Type| Type::Vector(Box::new(tp));
+        let types = [
+            Type::Bool,
+            Type::U8,
+            Type::U128,
+            Type::U256,
+            Type::Address,
+            Type::String,
+            vec_of(vec_of(Type::U64)),
+        ];
+        let (_, args) = prepare_function_signature(
+            &types,
+            &[
+                s("true"),
+                s("255"),
+                s("340282366920938463463374607431768211455"),
+                s("0xff"),
+                s("0x1"),
+                s("\"hello\""),
+                s("[[1, 2], []]"),
+            ],
+            true,
+            &Default::default(),
+        )
+        .unwrap();
+
+        for (tp, arg) in types.iter().zip(args) {
+            let bytes = bcs::to_bytes(&arg).unwrap();
+            assert_eq!(ScriptArg::decode(tp, &bytes).unwrap(), arg);
+        }
+
+        ScriptArg::decode(&Type::U64, &[1, 0, 0]).unwrap_err();
+        ScriptArg::decode(&Type::U8, &[1, 0]).unwrap_err();
+        ScriptArg::decode(&Type::Bool, &[2]).unwrap_err();
+    }
+
+    #[test]
+    fn test_int_args_types() {
+        let (signers, args) = prepare_function_signature(
+            &[Type::U256, Type::U256],
+            &[s("340282366920938463463374607431768211456"), s("0xff")],
+            true,
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(signers.len(), 0);
+        assert_eq!(
+            args,
+            vec![
+                ScriptArg::U256(U256::from(u128::MAX) + U256::one()),
+                ScriptArg::U256(U256::from(255u64)),
             ]
         );
+
+        let (_, args) = prepare_function_signature(
+            &[Type::Vector(Box::new(Type::U256))],
+            &[s("[4, 0x5]")],
+            true,
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            args,
+            vec![ScriptArg::Vector(vec![
+                ScriptArg::U256(U256::from(4u64)),
+                ScriptArg::U256(U256::from(5u64)),
+            ]),]
+        );
+
+        prepare_function_signature(&[Type::U8], &[s("256")], true, &Default::default())
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_string_args_types() {
+        let (_, args) = prepare_function_signature(
+            &[
+                Type::String,
+                Type::AsciiString,
+                Type::String,
+                Type::Vector(Box::new(Type::String)),
+            ],
+            &[
+                s(r#""привет""#),
+                s(r#""hello""#),
+                s(r#"b"plain\x21""#),
+                s(r#"["a", x"62"]"#),
+            ],
+            true,
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                ScriptArg::vector_u8("привет".as_bytes().to_vec()),
+                ScriptArg::vector_u8(b"hello".to_vec()),
+                ScriptArg::vector_u8(b"plain!".to_vec()),
+                ScriptArg::Vector(vec![
+                    ScriptArg::vector_u8(b"a".to_vec()),
+                    ScriptArg::vector_u8(b"b".to_vec()),
+                ]),
+            ]
+        );
+
+        prepare_function_signature(
+            &[Type::AsciiString],
+            &[s(r#""привет""#)],
+            true,
+            &Default::default(),
+        )
+        .unwrap_err();
+        prepare_function_signature(&[Type::String], &[s(r#"x"ff""#)], true, &Default::default())
+            .unwrap_err();
+        prepare_function_signature(&[Type::String], &[s("plain")], true, &Default::default())
+            .unwrap_err();
     }
 }
@@ -0,0 +1,318 @@
+use std::io::{self, BufRead, Write};
+use anyhow::Error;
+use itertools::Itertools;
+use move_package::source_package::parsed_manifest::AddressDeclarations;
+use lang::bytecode::info::{BytecodeInfo, Script, Type};
+use crate::context::Context;
+use crate::call::args_file::parameter_names;
+use crate::call::cmd::CallDeclarationCmd;
+use crate::call::fn_call::{find_modules, find_scripts, prepare_arg};
+use crate::call::parser::{parse_call, parse_tp_param, Call};
+use crate::cmd::call::get_package_from_path;
+
+/// Returns true if both stdin and stdout are attached to a terminal.
+pub fn is_interactive() -> bool {
+    atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout)
+}
+
+/// Builds the call arguments interactively from the function signature.
+/// Each type parameter and argument is prompted with its Move type and validated before use.
+///
+/// The call is left untouched if it already contains type parameters or arguments,
+/// or if the terminal is not interactive.
+pub fn prompt_call_args(ctx: &Context, cmd: &mut CallDeclarationCmd) -> Result<(), Error> {
+    if cmd.type_parameters.is_some()
+        || cmd.params.is_some()
+        || cmd.args_file.is_some()
+        || !is_interactive()
+    {
+        return Ok(());
+    }
+
+    let addr_map = ctx.address_declarations();
+    let call = parse_call(&addr_map, &cmd.call)?;
+    if !call.args().is_empty() || !call.tp_params().is_empty() {
+        return Ok(());
+    }
+
+    let (name, bytecode) = match &call {
+        Call::Script { name, .. } => (name, find_scripts(ctx, name, cmd.package.as_deref())?),
+        Call::Function {
+            address,
+            module,
+            func,
+            ..
+        } => (
+            func,
+            find_modules(ctx, *address, module, cmd.package.as_deref())?,
+        ),
+    };
+    let mut functions = bytecode
+        .into_iter()
+        .filter_map(|info| info.find_script_function(name.as_str()).map(|f| (info, f)))
+        .collect::<Vec<_>>();
+    ensure!(
+        !functions.is_empty(),
+        "Couldn't find a function with name {}.",
+        name
+    );
+
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut output = io::stdout();
+
+    let (info, script) = if functions.len() == 1 {
+        functions.remove(0)
+    } else {
+        let candidates = functions
+            .iter()
+            .map(|(info, script)| {
+                format!("{} ({})", signature(info, script), info.bytecode_ref().0)
+            })
+            .collect::<Vec<_>>();
+        let index = select_candidate(&mut input, &mut output, &candidates)?;
+        functions.remove(index)
+    };
+    if script.type_parameters.is_empty() && script.parameters.len() == signers_count(&script) {
+        return Ok(());
+    }
+
+    writeln!(output, "{}", signature(&info, &script))?;
+    if cmd.package.is_none() {
+        cmd.package = get_package_from_path(&info.bytecode_ref().0);
+    }
+
+    let names = parameter_names(&info, &script);
+    let (type_parameters, args) =
+        read_call_args(&mut input, &mut output, &script, names, &addr_map)?;
+    cmd.type_parameters = Some(type_parameters);
+    cmd.params = Some(args);
+    Ok(())
+}
+
+/// Reads the type parameters and the arguments of the function.
+/// `names` are the names of all function parameters, signers included.
+fn read_call_args<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    script: &Script,
+    names: Option<Vec<String>>,
+    addr_map: &AddressDeclarations,
+) -> Result<(Vec<String>, Vec<String>), Error> {
+    let type_parameters = script
+        .type_parameters
+        .iter()
+        .enumerate()
+        .map(|(index, abilities)| {
+            let abilities = abilities.abilities.iter().join(" + ");
+            let label = if abilities.is_empty() {
+                format!("T{}", index)
+            } else {
+                format!("T{}: {}", index, abilities)
+            };
+            prompt(input, output, &label, |value| {
+                parse_tp_param(addr_map, value).map(|_| ())
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let signers = signers_count(script);
+    let names = names
+        .filter(|names| names.len() == script.parameters.len())
+        .map(|names| names[signers..].to_vec());
+    let args = script.parameters[signers..]
+        .iter()
+        .enumerate()
+        .map(|(index, tp)| {
+            let label = match &names {
+                Some(names) => format!("{}: {}", names[index], tp),
+                None => format!("#{}: {}", index, tp),
+            };
+            prompt(input, output, &label, |value| {
+                validate_arg(tp, value, addr_map)
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok((type_parameters, args))
+}
+
+fn validate_arg(tp: &Type, value: &str, addr_map: &AddressDeclarations) -> Result<(), Error> {
+    prepare_arg(tp, value, addr_map).map(|_| ())
+}
+
+fn signers_count(script: &Script) -> usize {
+    script
+        .parameters
+        .iter()
+        .take_while(|tp| **tp == Type::Signer)
+        .count()
+}
+
+/// Returns the function signature in the Move syntax.
+fn signature(info: &BytecodeInfo, script: &Script) -> String {
+    let names = parameter_names(info, script)
+        .filter(|names| names.len() == script.parameters.len())
+        .unwrap_or_else(|| {
+            (0..script.parameters.len())
+                .map(|index| format!("arg{}", index))
+                .collect()
+        });
+    let type_parameters = if script.type_parameters.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "<{}>",
+            (0..script.type_parameters.len())
+                .map(|index| format!("T{}", index))
+                .join(", ")
+        )
+    };
+    let prefix = match info.address() {
+        Some(address) => format!("0x{}::{}::", address.short_str_lossless(), info.name()),
+        None => String::new(),
+    };
+    format!(
+        "{}{}{}({})",
+        prefix,
+        script.name,
+        type_parameters,
+        names
+            .iter()
+            .zip(&script.parameters)
+            .map(|(name, tp)| format!("{}: {}", name, tp))
+            .join(", ")
+    )
+}
+
+/// Prompts the user to select one of several functions with the same name.
+/// Returns the index of the selected candidate.
+fn select_candidate<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    candidates: &[String],
+) -> Result<usize, Error> {
+    writeln!(
+        output,
+        "More than one function with the given name was found:"
+    )?;
+    for (index, candidate) in candidates.iter().enumerate() {
+        writeln!(output, " {}. {}", index + 1, candidate)?;
+    }
+    let value = prompt(input, output, "Function number", |value| {
+        let number = value.parse::<usize>()?;
+        ensure!(
+            number > 0 && number <= candidates.len(),
+            "Expected a number from 1 to {}",
+            candidates.len()
+        );
+        Ok(())
+    })?;
+    Ok(value.parse::<usize>()? - 1)
+}
+
+/// Reads the value until it passes the validation.
+fn prompt<R, W, V>(
+    input: &mut R,
+    output: &mut W,
+    label: &str,
+    validate: V,
+) -> Result<String, Error>
+where
+    R: BufRead,
+    W: Write,
+    V: Fn(&str) -> Result<(), Error>,
+{
+    loop {
+        write!(output, "{}: ", label)?;
+        output.flush()?;
+
+        let mut buffer = String::new();
+        if input.read_line(&mut buffer)? == 0 {
+            bail!("Unexpected end of input");
+        }
+        let value = buffer.trim();
+        match validate(value) {
+            Ok(_) => return Ok(value.to_owned()),
+            Err(err) => writeln!(output, "Invalid value: {}", err)?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lang::bytecode::info::{Script, Type, TypeAbilities, TypeAbility};
+    use super::{read_call_args, select_candidate};
+
+    fn script() -> Script {
+        Script {
+            name: "transfer".to_string(),
+            index: 0,
+            parameters: vec![Type::Signer, Type::Address, Type::U8],
+            type_parameters: vec![TypeAbilities {
+                abilities: vec![TypeAbility::Store],
+            }],
+            returns: vec![],
+        }
+    }
+
+    #[test]
+    fn test_read_call_args() {
+        let mut input = "0x1::Coins::ETH\n0x2\n256\n255\n".as_bytes();
+        let mut output = vec![];
+        let names = ["account", "to", "amount"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        let (type_parameters, args) = read_call_args(
+            &mut input,
+            &mut output,
+            &script(),
+            Some(names),
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(type_parameters, vec!["0x1::Coins::ETH"]);
+        assert_eq!(args, vec!["0x2", "255"]);
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("T0: "));
+        assert!(output.contains("to: address: "));
+        // the invalid value is prompted again
+        assert_eq!(output.matches("amount: u8: ").count(), 2);
+        assert!(output.contains("Invalid value"));
+    }
+
+    #[test]
+    fn test_read_call_args_without_names() {
+        let mut input = "0x1::Coins::ETH\n0x2\n".as_bytes();
+        let mut output = vec![];
+        let err = read_call_args(
+            &mut input,
+            &mut output,
+            &script(),
+            None,
+            &Default::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unexpected end of input");
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("#0: address: "));
+        assert!(output.contains("#1: u8: "));
+    }
+
+    #[test]
+    fn test_select_candidate() {
+        let candidates = vec!["0x1::A::f()".to_string(), "0x2::A::f()".to_string()];
+        let mut input = "0\nfirst\n2\n".as_bytes();
+        let mut output = vec![];
+        assert_eq!(
+            select_candidate(&mut input, &mut output, &candidates).unwrap(),
+            1
+        );
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(" 2. 0x2::A::f()"));
+        assert_eq!(output.matches("Invalid value").count(), 2);
+    }
+}
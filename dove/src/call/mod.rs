@@ -1,16 +1,20 @@
 use anyhow::Error;
 use crate::context::Context;
 use crate::call::cmd::{CallDeclaration, CallDeclarationCmd};
-use crate::call::fn_call::{Config, make_function_call, make_script_call};
-use crate::call::model::EnrichedTransaction;
+use crate::call::fn_call::{CallArgs, Config, make_function_call, make_script_call};
+use crate::call::model::{Batch, EnrichedTransaction};
 use crate::call::parser::Call;
 
+/// Arguments file.
+pub mod args_file;
 /// Bytecode.
 pub mod bytecode;
 /// Command helper.
 pub mod cmd;
 /// Function call.
 pub mod fn_call;
+/// Interactive call builder.
+pub mod interactive;
 /// Transaction model.
 pub mod model;
 /// Call parser.
@@ -23,8 +27,16 @@ pub fn make_transaction(
     cfg: Config,
 ) -> Result<EnrichedTransaction, Error> {
     let address_decl = ctx.address_declarations();
-    let declaration = CallDeclaration::try_from((&address_decl, cmd))?;
-    match declaration.call {
+    let CallDeclaration {
+        call,
+        package,
+        args_file,
+    } = CallDeclaration::try_from((&address_decl, cmd))?;
+    let call_args = |args| match args_file {
+        Some(file) => CallArgs::File(file),
+        None => CallArgs::Line(args),
+    };
+    match call {
         Call::Function {
             address,
             module,
@@ -38,8 +50,8 @@ pub fn make_transaction(
             module,
             func,
             type_tag,
-            args,
-            declaration.package,
+            call_args(args),
+            package,
             cfg,
         ),
         Call::Script {
@@ -51,9 +63,47 @@ pub fn make_transaction(
             &address_decl,
             name,
             type_tag,
-            args,
-            declaration.package,
+            call_args(args),
+            package,
             cfg,
         ),
     }
 }
+
+/// Make an ordered batch of chain transactions with given call declarations.
+pub fn make_batch(
+    ctx: &Context,
+    cmds: Vec<CallDeclarationCmd>,
+    cfg: Config,
+) -> Result<EnrichedTransaction, Error> {
+    ensure!(
+        !cmds.is_empty(),
+        "The batch must contain at least one call."
+    );
+
+    let mut first = None;
+    let mut transactions = Vec::with_capacity(cmds.len());
+    for (index, cmd) in cmds.into_iter().enumerate() {
+        let call = cmd.call.clone();
+        match make_transaction(ctx, cmd, cfg.clone())
+            .map_err(|err| anyhow!("Call {} '{}': {}", index, call, err))?
+        {
+            EnrichedTransaction::Global { bi, tx, name } => {
+                if first.is_none() {
+                    first = Some((bi, name));
+                }
+                transactions.push(tx);
+            }
+            EnrichedTransaction::Local { .. } | EnrichedTransaction::Batch { .. } => {
+                bail!("Call {} '{}': chain transaction is expected.", index, call)
+            }
+        }
+    }
+
+    let (bi, name) = first.ok_or_else(|| anyhow!("The batch is empty."))?;
+    Ok(EnrichedTransaction::Batch {
+        bi,
+        batch: Batch { transactions },
+        name: format!("{}_batch", name),
+    })
+}
@@ -1,19 +1,31 @@
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::TypeTag;
 use anyhow::Error;
-use move_core_types::value::MoveValue;
+use std::fmt;
 use std::str::FromStr;
 use move_core_types::account_address::AccountAddress;
-use move_core_types::transaction_argument::TransactionArgument;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use move_symbol_pool::Symbol;
-use lang::bytecode::info::BytecodeInfo;
+use lang::bytecode::info::{BytecodeInfo, Type};
+use crate::natives::U256;
+
+/// Number of known transaction versions.
+const TX_VERSIONS: u8 = 2;
 
 /// Transaction model.
 #[derive(Serialize, Deserialize, Debug)]
 pub enum Transaction {
     /// Version 1.
     V1(V1),
+    /// Version 2.
+    V2(V2),
+}
+
+/// Ordered batch of transactions.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Batch {
+    /// Transactions in the execution order.
+    pub transactions: Vec<Transaction>,
 }
 
 /// Transaction model.
@@ -29,6 +41,44 @@ pub struct V1 {
     pub type_args: Vec<TypeTag>,
 }
 
+/// Transaction model with replay protection.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct V2 {
+    /// Signers.
+    pub signers: Vec<Signer>,
+    /// Call declaration.
+    pub call: Call,
+    /// Script args.
+    pub args: Vec<Vec<u8>>,
+    /// Script type arguments.
+    pub type_args: Vec<TypeTag>,
+    /// Transaction metadata.
+    pub meta: TxMetadata,
+}
+
+/// Transaction metadata.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct TxMetadata {
+    /// Chain or network id the transaction is intended for.
+    pub chain_id: Option<String>,
+    /// The block after which the transaction is no longer valid.
+    pub expiration_block: Option<u64>,
+    /// Free-form memo.
+    pub memo: Option<String>,
+}
+
+impl From<V1> for V2 {
+    fn from(tx: V1) -> Self {
+        V2 {
+            signers: tx.signers,
+            call: tx.call,
+            args: tx.args,
+            type_args: tx.type_args,
+            meta: TxMetadata::default(),
+        }
+    }
+}
+
 /// Call declaration.
 #[derive(Serialize, Deserialize, Debug)]
 pub enum Call {
@@ -86,24 +136,47 @@ impl Transaction {
     }
 
     fn make_args(args: Vec<ScriptArg>) -> Result<Vec<Vec<u8>>, Error> {
-        args.into_iter()
-            .map(ScriptArg::into)
-            .map(|val: MoveValue| bcs::to_bytes(&val))
-            .collect::<Result<_, _>>()
-            .map_err(Error::msg)
+        encode_args(&args)
     }
 
-    /// Returns last version.
-    pub fn inner_mut(&mut self) -> &mut V1 {
+    /// Converts the transaction to version 2 with the given metadata.
+    /// Version 1 is kept if the metadata is empty: the node decodes version 1 only.
+    pub fn with_metadata(self, meta: TxMetadata) -> Transaction {
+        if meta == TxMetadata::default() {
+            return self;
+        }
+        let mut tx = self.inner();
+        tx.meta = meta;
+        Transaction::V2(tx)
+    }
+
+    /// Decodes BCS-encoded transaction of any known version.
+    pub fn decode(bytes: &[u8]) -> Result<Transaction, Error> {
+        let version = bytes
+            .first()
+            .ok_or_else(|| anyhow!("Failed to decode transaction: empty input"))?;
+        // The first byte is the BCS variant index: 0 for V1, 1 for V2, etc.
+        ensure!(
+            *version < TX_VERSIONS,
+            "Unsupported transaction version: {}. Please update dove.",
+            *version as u16 + 1
+        );
+        bcs::from_bytes(bytes).map_err(|err| anyhow!("Failed to decode transaction: {}", err))
+    }
+
+    /// Returns transaction version.
+    pub fn version(&self) -> u8 {
         match self {
-            Transaction::V1(v) => v,
+            Transaction::V1(_) => 1,
+            Transaction::V2(_) => 2,
         }
     }
 
     /// Returns last version.
-    pub fn inner(self) -> V1 {
+    pub fn inner(self) -> V2 {
         match self {
-            Transaction::V1(v) => v,
+            Transaction::V1(v) => v.into(),
+            Transaction::V2(v) => v,
         }
     }
 }
@@ -117,66 +190,130 @@ pub enum ScriptArg {
     U64(u64),
     /// u128
     U128(u128),
+    /// 0x1::U256::U256
+    U256(U256),
     /// bool
     Bool(bool),
     /// address
     Address(AccountAddress),
-    /// vector<u8>
-    VectorU8(Vec<u8>),
-    /// vector<u64>
-    VectorU64(Vec<u64>),
-    /// vector<u128>
-    VectorU128(Vec<u128>),
-    /// vector<bool>
-    VectorBool(Vec<bool>),
-    /// vector<address>
-    VectorAddress(Vec<AccountAddress>),
-}
-
-impl From<ScriptArg> for MoveValue {
-    fn from(arg: ScriptArg) -> Self {
-        match arg {
-            ScriptArg::U8(val) => MoveValue::U8(val),
-            ScriptArg::U64(val) => MoveValue::U64(val),
-            ScriptArg::U128(val) => MoveValue::U128(val),
-            ScriptArg::Bool(val) => MoveValue::Bool(val),
-            ScriptArg::Address(val) => MoveValue::Address(val),
-            ScriptArg::VectorU8(val) => MoveValue::vector_u8(val),
-            ScriptArg::VectorU64(val) => {
-                MoveValue::Vector(val.into_iter().map(MoveValue::U64).collect())
+    /// vector<T> of any element type, including nested vectors.
+    Vector(Vec<ScriptArg>),
+}
+
+impl ScriptArg {
+    /// Creates vector<u8> argument.
+    pub fn vector_u8(bytes: Vec<u8>) -> ScriptArg {
+        ScriptArg::Vector(bytes.into_iter().map(ScriptArg::U8).collect())
+    }
+
+    /// Decodes BCS-encoded argument of the given type.
+    pub fn decode(tp: &Type, bytes: &[u8]) -> Result<ScriptArg, Error> {
+        let mut reader = bytes;
+        let arg = Self::read(tp, &mut reader)?;
+        ensure!(
+            reader.is_empty(),
+            "Failed to decode {}: {} unexpected trailing bytes",
+            tp,
+            reader.len()
+        );
+        Ok(arg)
+    }
+
+    fn read(tp: &Type, reader: &mut &[u8]) -> Result<ScriptArg, Error> {
+        fn take<'a>(reader: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+            ensure!(reader.len() >= len, "Unexpected end of input");
+            let (head, tail) = reader.split_at(len);
+            *reader = tail;
+            Ok(head)
+        }
+
+        /// Reads ULEB128-encoded sequence length.
+        fn read_len(reader: &mut &[u8]) -> Result<usize, Error> {
+            let mut len = 0;
+            for shift in (0..32).step_by(7) {
+                let byte = take(reader, 1)?[0];
+                len |= ((byte & 0x7f) as usize) << shift;
+                if byte & 0x80 == 0 {
+                    return Ok(len);
+                }
             }
-            ScriptArg::VectorU128(val) => {
-                MoveValue::Vector(val.into_iter().map(MoveValue::U128).collect())
+            bail!("Invalid sequence length")
+        }
+
+        macro_rules! read_int {
+            ($script_arg:expr, $tp:ty) => {{
+                let mut buf = [0; std::mem::size_of::<$tp>()];
+                buf.copy_from_slice(take(reader, buf.len())?);
+                $script_arg(<$tp>::from_le_bytes(buf))
+            }};
+        }
+
+        Ok(match tp {
+            Type::Bool => match take(reader, 1)?[0] {
+                0 => ScriptArg::Bool(false),
+                1 => ScriptArg::Bool(true),
+                val => bail!("Invalid bool value: {}", val),
+            },
+            Type::U8 => read_int!(ScriptArg::U8, u8),
+            Type::U64 => read_int!(ScriptArg::U64, u64),
+            Type::U128 => read_int!(ScriptArg::U128, u128),
+            Type::U256 => {
+                ensure!(read_len(reader)? == 32, "Invalid U256 length");
+                ScriptArg::U256(U256::from_little_endian(take(reader, 32)?))
             }
-            ScriptArg::VectorBool(val) => {
-                MoveValue::Vector(val.into_iter().map(MoveValue::Bool).collect())
+            Type::Address => {
+                let mut addr = [0; AccountAddress::LENGTH];
+                addr.copy_from_slice(take(reader, AccountAddress::LENGTH)?);
+                ScriptArg::Address(AccountAddress::new(addr))
             }
-            ScriptArg::VectorAddress(val) => {
-                MoveValue::Vector(val.into_iter().map(MoveValue::Address).collect())
+            Type::String | Type::AsciiString => {
+                let len = read_len(reader)?;
+                ScriptArg::vector_u8(take(reader, len)?.to_vec())
             }
-        }
+            Type::Vector(tp) => {
+                let len = read_len(reader)?;
+                ScriptArg::Vector(
+                    (0..len)
+                        .map(|_| Self::read(tp, reader))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            Type::Signer
+            | Type::Struct(_)
+            | Type::Reference(_)
+            | Type::MutableReference(_)
+            | Type::TypeParameter(_) => bail!("Unexpected script parameter: {}", tp),
+        })
     }
 }
 
-impl TryInto<TransactionArgument> for ScriptArg {
-    type Error = Error;
-
-    fn try_into(self) -> Result<TransactionArgument, Self::Error> {
-        Ok(match self {
-            ScriptArg::U8(val) => TransactionArgument::U8(val),
-            ScriptArg::U64(val) => TransactionArgument::U64(val),
-            ScriptArg::U128(val) => TransactionArgument::U128(val),
-            ScriptArg::Bool(val) => TransactionArgument::Bool(val),
-            ScriptArg::Address(val) => TransactionArgument::Address(val),
-            ScriptArg::VectorU8(val) => TransactionArgument::U8Vector(val),
-            ScriptArg::VectorU64(_)
-            | ScriptArg::VectorU128(_)
-            | ScriptArg::VectorBool(_)
-            | ScriptArg::VectorAddress(_) => bail!("Unsupported transaction args."),
-        })
+impl Serialize for ScriptArg {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ScriptArg::U8(val) => serializer.serialize_u8(*val),
+            ScriptArg::U64(val) => serializer.serialize_u64(*val),
+            ScriptArg::U128(val) => serializer.serialize_u128(*val),
+            ScriptArg::U256(val) => {
+                // `U256` is a struct with a single field: 32 little-endian bytes.
+                let mut bytes = vec![0; 32];
+                val.to_little_endian(&mut bytes);
+                serializer.serialize_newtype_struct("U256", &bytes)
+            }
+            ScriptArg::Bool(val) => serializer.serialize_bool(*val),
+            ScriptArg::Address(val) => val.serialize(serializer),
+            ScriptArg::Vector(val) => serializer.collect_seq(val),
+        }
     }
 }
 
+/// Returns BCS-encoded script arguments.
+pub fn encode_args(args: &[ScriptArg]) -> Result<Vec<Vec<u8>>, Error> {
+    args.iter()
+        .map(bcs::to_bytes)
+        .collect::<Result<_, _>>()
+        .map_err(Error::msg)
+}
+
 /// Signer type.
 #[derive(Serialize, Deserialize, Debug, Ord, PartialOrd, Eq, PartialEq)]
 pub enum Signer {
@@ -188,6 +325,16 @@ pub enum Signer {
     Name(Symbol),
 }
 
+impl fmt::Display for Signer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Signer::Root => write!(f, "root"),
+            Signer::Placeholder => write!(f, "_"),
+            Signer::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
 impl FromStr for Signer {
     type Err = Error;
 
@@ -223,12 +370,12 @@ pub enum EnrichedTransaction {
     Local {
         /// Bytecode info.
         bi: BytecodeInfo,
-        /// Script args.
-        args: Vec<ScriptArg>,
+        /// Call declaration.
+        call: Call,
+        /// BCS-encoded script args.
+        args: Vec<Vec<u8>>,
         /// Type tags.
         type_tag: Vec<TypeTag>,
-        /// Function name.
-        func_name: Option<String>,
         /// Signers.
         signers: Vec<AccountAddress>,
     },
@@ -241,4 +388,61 @@ pub enum EnrichedTransaction {
         /// Transaction name.
         name: String,
     },
+    /// An ordered batch of transactions intended for execution in the chain executor.
+    Batch {
+        /// Bytecode info of the first call.
+        bi: BytecodeInfo,
+        /// Transactions.
+        batch: Batch,
+        /// Batch name.
+        name: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::call::model::{Call, Signer, Transaction, TxMetadata, V1};
+
+    fn v1() -> V1 {
+        V1 {
+            signers: vec![Signer::Placeholder],
+            call: Call::Script { code: vec![1, 2] },
+            args: vec![vec![1]],
+            type_args: vec![],
+        }
+    }
+
+    #[test]
+    fn test_decode_tx() {
+        let bytes = bcs::to_bytes(&Transaction::V1(v1())).unwrap();
+        let tx = Transaction::decode(&bytes).unwrap();
+        assert_eq!(tx.version(), 1);
+        let tx = tx.inner();
+        assert_eq!(tx.meta, TxMetadata::default());
+        assert_eq!(tx.args, vec![vec![1]]);
+
+        let meta = TxMetadata {
+            chain_id: Some("pontem-testnet".to_owned()),
+            expiration_block: Some(100),
+            memo: Some("memo".to_owned()),
+        };
+        let bytes = bcs::to_bytes(&Transaction::V1(v1()).with_metadata(meta.clone())).unwrap();
+        let tx = Transaction::decode(&bytes).unwrap();
+        assert_eq!(tx.version(), 2);
+        assert_eq!(tx.inner().meta, meta);
+
+        let mut bytes = bytes;
+        bytes[0] = 2;
+        let err = Transaction::decode(&bytes).unwrap_err().to_string();
+        assert!(
+            err.contains("Unsupported transaction version: 3"),
+            "{}",
+            err
+        );
+        Transaction::decode(&[]).unwrap_err();
+
+        // empty metadata keeps version 1
+        let tx = Transaction::V1(v1()).with_metadata(TxMetadata::default());
+        assert_eq!(tx.version(), 1);
+    }
 }
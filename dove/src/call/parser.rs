@@ -5,7 +5,6 @@ use move_core_types::identifier::Identifier;
 use move_compiler::parser::lexer::{Lexer, Tok};
 use move_compiler::parser::syntax::{parse_type, parse_address_bytes, consume_token, Context};
 use lang::lexer::unwrap_spanned_ty;
-use std::str::FromStr;
 use move_command_line_common::files::FileHash;
 use move_compiler::Flags;
 use move_compiler::shared::CompilationEnv;
@@ -57,6 +56,20 @@ impl Call {
         }
     }
 
+    pub(crate) fn args(&self) -> &[String] {
+        match self {
+            Call::Function { args, .. } => args,
+            Call::Script { args, .. } => args,
+        }
+    }
+
+    pub(crate) fn tp_params(&self) -> &[TypeTag] {
+        match self {
+            Call::Function { type_tag, .. } => type_tag,
+            Call::Script { type_tag, .. } => type_tag,
+        }
+    }
+
     pub(crate) fn set_tp_params(&mut self, new_tags: Vec<TypeTag>) {
         match self {
             Call::Function { type_tag, .. } => {
@@ -110,7 +123,8 @@ impl Call {
 /// Parse call
 /// Return: Ok(Script name, Type parameters, Function arguments) or Error
 pub(crate) fn parse_call(addr_map: &AddressDeclarations, call: &str) -> Result<Call, Error> {
-    let mut lexer = Lexer::new(call, FileHash::new("call"));
+    let call = escape_string_literals(call)?;
+    let mut lexer = Lexer::new(&call, FileHash::new("call"));
     let mut env = CompilationEnv::new(Flags::empty());
     let mut ctx = Context::new(&mut env, &mut lexer);
 
@@ -273,13 +287,22 @@ fn parse_args(ctx: &mut Context) -> Result<Vec<String>, Error> {
             let sw = ctx.tokens.peek() == Tok::LBracket;
             ctx.tokens.advance().map_err(map_err)?;
             if sw {
-                while ctx.tokens.peek() != Tok::RBracket {
+                let mut depth = 1;
+                loop {
+                    match ctx.tokens.peek() {
+                        Tok::LBracket => depth += 1,
+                        Tok::RBracket => depth -= 1,
+                        Tok::EOF => anyhow::bail!("{}", &error_message),
+                        _ => {}
+                    }
                     token.push_str(ctx.tokens.content());
+                    if depth == 0 {
+                        break;
+                    }
                     ctx.tokens
                         .advance()
                         .map_err(|_| anyhow!("{}", &error_message))?;
                 }
-                token.push_str(ctx.tokens.content());
             } else {
                 while ctx.tokens.peek() != Tok::Comma && ctx.tokens.peek() != Tok::RParen {
                     token.push_str(ctx.tokens.content());
@@ -322,15 +345,37 @@ pub(crate) fn parse_type_param(
     unwrap_spanned_ty(addr_map, ty)
 }
 
-pub(crate) fn parse_vec<E>(tkn: &str, tp_name: &str) -> Result<Vec<E>, Error>
-where
-    E: FromStr,
-{
-    let map_err = |err| Error::msg(format!("{:?}", err));
+/// Argument literal: a single value or a list of literals in format [n1, [n2, n3], ..., nn].
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum ArgLiteral {
+    /// Single value.
+    Value(String),
+    /// List of values.
+    List(Vec<ArgLiteral>),
+}
 
-    let mut lexer = Lexer::new(tkn, FileHash::new("vec"));
+/// Parse argument literal.
+/// Nested lists are supported at any depth.
+pub(crate) fn parse_literal(arg: &str) -> Result<ArgLiteral, Error> {
+    if !arg.trim_start().starts_with('[') {
+        return Ok(ArgLiteral::Value(arg.to_string()));
+    }
 
-    lexer.advance().map_err(map_err)?;
+    let arg = escape_string_literals(arg)?;
+    let mut lexer = Lexer::new(&arg, FileHash::new("vec"));
+    lexer
+        .advance()
+        .map_err(|err| Error::msg(format!("{:?}", err)))?;
+
+    let literal = parse_list(&mut lexer)?;
+    if lexer.peek() != Tok::EOF {
+        anyhow::bail!("Unexpected '{}' after the end of vector.", lexer.content());
+    }
+    Ok(literal)
+}
+
+fn parse_list(lexer: &mut Lexer) -> Result<ArgLiteral, Error> {
+    let map_err = |err| Error::msg(format!("{:?}", err));
 
     if lexer.peek() != Tok::LBracket {
         anyhow::bail!("Vector in format  [n1, n2, ..., nn] is expected.");
@@ -338,28 +383,171 @@ where
     lexer.advance().map_err(map_err)?;
 
     let mut elements = vec![];
-    while lexer.peek() != Tok::RBracket {
+    loop {
         match lexer.peek() {
-            Tok::Comma => {
+            // empty vector or trailing comma
+            Tok::RBracket => {
                 lexer.advance().map_err(map_err)?;
-                continue;
+                break;
+            }
+            Tok::Comma => {
+                anyhow::bail!("Empty vector element.");
             }
             Tok::EOF => {
                 anyhow::bail!("unexpected end of vector.");
             }
+            Tok::LBracket => {
+                elements.push(parse_list(lexer)?);
+            }
             _ => {
-                elements.push(E::from_str(lexer.content()).map_err(|_| {
-                    anyhow!(
-                        "Failed to parse vector element. {} type is expected. Actual:'{}'",
-                        tp_name,
-                        lexer.content()
-                    )
-                })?);
+                // Values like hex blobs or ss58 addresses are split into several adjacent tokens.
+                let mut value = lexer.content().to_owned();
                 lexer.advance().map_err(map_err)?;
+                while !matches!(
+                    lexer.peek(),
+                    Tok::Comma | Tok::LBracket | Tok::RBracket | Tok::EOF
+                ) && lexer.start_loc() == lexer.previous_end_loc()
+                {
+                    value.push_str(lexer.content());
+                    lexer.advance().map_err(map_err)?;
+                }
+                elements.push(ArgLiteral::Value(value));
             }
         }
+
+        match lexer.peek() {
+            Tok::Comma => {
+                lexer.advance().map_err(map_err)?;
+            }
+            Tok::RBracket => {
+                lexer.advance().map_err(map_err)?;
+                break;
+            }
+            Tok::EOF => {
+                anyhow::bail!("unexpected end of vector.");
+            }
+            _ => {
+                anyhow::bail!(
+                    "Expected ',' or ']' after the vector element, found '{}'.",
+                    lexer.content()
+                );
+            }
+        }
+    }
+    Ok(ArgLiteral::List(elements))
+}
+
+/// Replaces quoted string literals ("hello") and byte strings (b"hello")
+/// with hex byte strings (x"68656c6c6f"), which can be processed by the move lexer.
+/// Hex byte strings (x"...") are kept as is.
+fn escape_string_literals(value: &str) -> Result<String, Error> {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    let mut prev = None;
+
+    while let Some(ch) = chars.next() {
+        if ch != '"' {
+            result.push(ch);
+            prev = Some(ch);
+            continue;
+        }
+
+        match prev {
+            Some('x') => {
+                let mut literal = String::new();
+                let mut closed = false;
+                for ch in chars.by_ref() {
+                    if ch == '"' {
+                        closed = true;
+                        break;
+                    }
+                    literal.push(ch);
+                }
+                ensure!(closed, "Unterminated string literal in '{}'.", value);
+                result.push('"');
+                result.push_str(&literal);
+                result.push('"');
+            }
+            Some('b') => {
+                result.pop();
+                let bytes = read_literal(&mut chars, true, value)?;
+                result.push_str(&format!("x\"{}\"", hex::encode(bytes)));
+            }
+            _ => {
+                let bytes = read_literal(&mut chars, false, value)?;
+                result.push_str(&format!("x\"{}\"", hex::encode(bytes)));
+            }
+        }
+        prev = Some('"');
+    }
+    Ok(result)
+}
+
+/// Reads the literal up to the closing quote and processes the escape sequences:
+/// \n, \r, \t, \0, \\, \" and \xHH (byte strings only).
+fn read_literal(
+    chars: &mut std::str::Chars,
+    is_byte_string: bool,
+    value: &str,
+) -> Result<Vec<u8>, Error> {
+    let mut literal = Vec::new();
+    while let Some(ch) = chars.next() {
+        let ch = match ch {
+            '"' => return Ok(literal),
+            '\\' => match chars.next() {
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('t') => '\t',
+                Some('0') => '\0',
+                Some('\\') => '\\',
+                Some('"') => '"',
+                Some('x') if is_byte_string => {
+                    let hex = chars.by_ref().take(2).collect::<String>();
+                    let byte = u8::from_str_radix(&hex, 16)
+                        .ok()
+                        .filter(|_| hex.len() == 2)
+                        .ok_or_else(|| {
+                            anyhow!("Invalid escape sequence '\\x{}' in byte string.", hex)
+                        })?;
+                    literal.push(byte);
+                    continue;
+                }
+                Some(ch) => {
+                    anyhow::bail!("Invalid escape sequence '\\{}' in string literal.", ch)
+                }
+                None => break,
+            },
+            ch => ch,
+        };
+        if is_byte_string {
+            ensure!(
+                ch.is_ascii(),
+                "Non-ASCII character '{}' in byte string. Use the \\xHH escape sequence.",
+                ch
+            );
+        }
+        let mut buf = [0; 4];
+        literal.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+    }
+    anyhow::bail!("Unterminated string literal in '{}'.", value)
+}
+
+/// Parse string literal into bytes.
+/// Supported formats: "hello", b"hello" and x"68656c6c6f".
+pub(crate) fn parse_string_literal(value: &str) -> Result<Vec<u8>, Error> {
+    let escaped = escape_string_literals(value.trim())?;
+    match escaped
+        .strip_prefix("x\"")
+        .and_then(|v| v.strip_suffix('"'))
+    {
+        Some(hex) => {
+            hex::decode(hex).map_err(|err| anyhow!("Invalid hex string {}: {}", value, err))
+        }
+        None => anyhow::bail!(
+            r#"Expected a quoted string literal: "hello", b"hello" or x"68656c6c6f", got {}."#,
+            value
+        ),
     }
-    Ok(elements)
 }
 
 #[cfg(test)]
@@ -370,7 +558,7 @@ mod tests_call_parser {
     use move_core_types::language_storage::{StructTag, TypeTag};
     use move_core_types::language_storage::CORE_CODE_ADDRESS;
     use move_symbol_pool::Symbol;
-    use crate::call::parser::parse_call;
+    use crate::call::parser::{parse_call, parse_literal, parse_string_literal, ArgLiteral};
 
     #[test]
     fn func_call() {
@@ -546,4 +734,126 @@ mod tests_call_parser {
         );
         parse_call(&map, "Core::Diem::create_account(Std)").unwrap();
     }
+
+    #[test]
+    fn nested_args() {
+        let (_, _, args) = parse_call(
+            &Default::default(),
+            "create_account([[1, 2], [], [3]], [0102, 68656c6c6f], 10)",
+        )
+        .unwrap()
+        .script();
+        assert_eq!(
+            args,
+            vec![
+                "[[1,2],[],[3]]".to_owned(),
+                "[0102,68656c6c6f]".to_owned(),
+                "10".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn literal() {
+        fn v(val: &str) -> ArgLiteral {
+            ArgLiteral::Value(val.to_owned())
+        }
+
+        assert_eq!(parse_literal("10").unwrap(), v("10"));
+        assert_eq!(parse_literal("[]").unwrap(), ArgLiteral::List(vec![]));
+        assert_eq!(
+            parse_literal("[[1, 2], [], [0x1]]").unwrap(),
+            ArgLiteral::List(vec![
+                ArgLiteral::List(vec![v("1"), v("2")]),
+                ArgLiteral::List(vec![]),
+                ArgLiteral::List(vec![v("0x1")]),
+            ])
+        );
+        assert_eq!(
+            parse_literal("[68656c6c6f, 0102]").unwrap(),
+            ArgLiteral::List(vec![v("68656c6c6f"), v("0102")])
+        );
+        parse_literal("[[1, 2]").unwrap_err();
+        parse_literal("[1, 2]]").unwrap_err();
+        assert_eq!(
+            parse_literal("[5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY, 0x1]").unwrap(),
+            ArgLiteral::List(vec![
+                v("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+                v("0x1")
+            ])
+        );
+        // elements must be separated by commas
+        parse_literal("[1 2]").unwrap_err();
+        parse_literal("[[1] [2]]").unwrap_err();
+        // empty elements are rejected, the trailing comma is allowed
+        parse_literal("[1,,2]").unwrap_err();
+        parse_literal("[,1]").unwrap_err();
+        assert_eq!(
+            parse_literal("[1, 2,]").unwrap(),
+            ArgLiteral::List(vec![v("1"), v("2")])
+        );
+    }
+
+    #[test]
+    fn string_args() {
+        let (_, _, args) = parse_call(
+            &Default::default(),
+            r#"create_account("hello, world", ["a", "\"b\""], x"0102", 10)"#,
+        )
+        .unwrap()
+        .script();
+        assert_eq!(args.len(), 4);
+        assert_eq!(
+            parse_string_literal(&args[0]).unwrap(),
+            b"hello, world".to_vec()
+        );
+        assert_eq!(
+            parse_literal(&args[1]).unwrap(),
+            ArgLiteral::List(vec![
+                ArgLiteral::Value(r#"x"61""#.to_owned()),
+                ArgLiteral::Value(r#"x"226222""#.to_owned()),
+            ])
+        );
+        assert_eq!(parse_string_literal(&args[2]).unwrap(), vec![1, 2]);
+        assert_eq!(args[3], "10");
+
+        assert_eq!(parse_string_literal(r#""a\tb""#).unwrap(), b"a\tb".to_vec());
+        parse_string_literal(r#""hello"#).unwrap_err();
+        // quotes are required
+        parse_string_literal("hello").unwrap_err();
+        parse_string_literal("").unwrap_err();
+        parse_call(&Default::default(), r#"create_account("hello)"#).unwrap_err();
+    }
+
+    #[test]
+    fn byte_string_args() {
+        assert_eq!(
+            parse_string_literal(r#"b"hello""#).unwrap(),
+            b"hello".to_vec()
+        );
+        assert_eq!(
+            parse_string_literal(r#"b"a\n\"b\"\x00\xfF\\""#).unwrap(),
+            b"a\n\"b\"\x00\xff\\".to_vec()
+        );
+        parse_string_literal(r#"b"\x1""#).unwrap_err();
+        parse_string_literal(r#"b"\xzz""#).unwrap_err();
+        parse_string_literal(r#"b"\q""#).unwrap_err();
+        parse_string_literal(r#"b"привет""#).unwrap_err();
+        parse_string_literal(r#"b"hello"#).unwrap_err();
+
+        let (_, _, args) = parse_call(
+            &Default::default(),
+            r#"create_account(b"a\x62", [b"\"", x"01"])"#,
+        )
+        .unwrap()
+        .script();
+        assert_eq!(parse_string_literal(&args[0]).unwrap(), b"ab".to_vec());
+        assert_eq!(
+            parse_literal(&args[1]).unwrap(),
+            ArgLiteral::List(vec![
+                ArgLiteral::Value(r#"x"22""#.to_owned()),
+                ArgLiteral::Value(r#"x"01""#.to_owned()),
+            ])
+        );
+    }
 }
@@ -0,0 +1,131 @@
+use std::fs;
+use std::path::Path;
+use anyhow::{Error, Result};
+use better_any::{Tid, TidAble};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+/// Name of the chain environment file in the sandbox storage directory.
+pub(crate) const CHAIN_ENV_FILE: &str = "chain_env.json";
+
+/// Block height and timestamp seen by the `Block` and `Timestamp` natives.
+/// The natives read the environment from the native context extensions.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Tid)]
+pub struct ChainEnv {
+    /// Current block height.
+    pub block_height: u64,
+    /// Current timestamp in microseconds.
+    pub timestamp: u64,
+    /// Block time in microseconds. Used to move the timestamp forward with the blocks.
+    pub block_time: u64,
+}
+
+impl Default for ChainEnv {
+    fn default() -> Self {
+        ChainEnv {
+            block_height: 1,
+            timestamp: 0,
+            block_time: 6_000_000,
+        }
+    }
+}
+
+impl ChainEnv {
+    /// Moves the block height and the timestamp forward.
+    pub fn advance(&mut self, blocks: u64) {
+        self.block_height = self.block_height.saturating_add(blocks);
+        self.timestamp = self
+            .timestamp
+            .saturating_add(blocks.saturating_mul(self.block_time));
+    }
+
+    /// Loads the environment stored in the sandbox storage directory.
+    pub fn load(storage_dir: &Path) -> Result<Option<ChainEnv>, Error> {
+        let path = storage_dir.join(CHAIN_ENV_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let env = serde_json::from_str(&fs::read_to_string(&path)?)
+            .map_err(|err| anyhow!("Failed to parse {:?}: {}", path, err))?;
+        Ok(Some(env))
+    }
+
+    /// Stores the environment in the sandbox storage directory.
+    pub fn save(&self, storage_dir: &Path) -> Result<(), Error> {
+        fs::create_dir_all(storage_dir)?;
+        fs::write(
+            storage_dir.join(CHAIN_ENV_FILE),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        Ok(())
+    }
+}
+
+/// Chain environment options of `dove run` and `dove test`.
+#[derive(Args, Debug, Default, Clone)]
+pub struct ChainEnvArgs {
+    /// Block height returned by `Block::get_current_block_height`.
+    #[clap(long = "block-height")]
+    pub block_height: Option<u64>,
+
+    /// Timestamp in microseconds returned by `Timestamp::now_microseconds`.
+    #[clap(long = "timestamp")]
+    pub timestamp: Option<u64>,
+
+    /// Block time in microseconds [default: 6000000].
+    #[clap(long = "block-time")]
+    pub block_time: Option<u64>,
+
+    /// Number of blocks to move forward before the execution.
+    /// `dove run` keeps the environment in the sandbox storage, so the blocks add up between runs.
+    #[clap(long = "advance-blocks")]
+    pub advance_blocks: Option<u64>,
+}
+
+impl ChainEnvArgs {
+    /// Overrides the environment with the options.
+    pub fn apply(&self, mut env: ChainEnv) -> ChainEnv {
+        if let Some(block_height) = self.block_height {
+            env.block_height = block_height;
+        }
+        if let Some(timestamp) = self.timestamp {
+            env.timestamp = timestamp;
+        }
+        if let Some(block_time) = self.block_time {
+            env.block_time = block_time;
+        }
+        if let Some(blocks) = self.advance_blocks {
+            env.advance(blocks);
+        }
+        env
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChainEnv, ChainEnvArgs};
+
+    #[test]
+    fn test_apply_args() {
+        let args = ChainEnvArgs {
+            block_height: Some(100),
+            timestamp: None,
+            block_time: Some(1_000),
+            advance_blocks: Some(5),
+        };
+        let env = args.apply(ChainEnv {
+            block_height: 1,
+            timestamp: 10_000,
+            block_time: 6_000_000,
+        });
+        assert_eq!(
+            env,
+            ChainEnv {
+                block_height: 105,
+                timestamp: 15_000,
+                block_time: 1_000,
+            }
+        );
+        assert_eq!(ChainEnvArgs::default().apply(env), env);
+    }
+}
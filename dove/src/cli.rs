@@ -2,23 +2,32 @@ use std::env;
 use std::path::{PathBuf, Path};
 
 use anyhow::{Result, Error};
-use clap::Parser;
+use clap::{Args, CommandFactory, FromArgMatches, Parser};
 use semver::{Version, VersionReq};
 
 use move_cli::{Move};
+use move_cli::package::cli::PackageCommand;
 use move_core_types::errmap::ErrorMapping;
+use move_unit_test::extensions::set_extension_hook;
 
 use crate::{
     DOVE_VERSION, DOVE_HASH, MOVE_STDLIB_VERSION, DIEM_VERSION, DIEM_HASH, ERROR_DESCRIPTIONS,
 };
+use crate::chain_env::ChainEnv;
+use crate::cmd::bench_natives::BenchNatives;
 use crate::cmd::clean::Clean;
-use crate::cmd::run::Run;
+use crate::cmd::run::{ExecutionArgs, Run};
 use crate::cmd::call::ExecuteTransaction;
 use crate::cmd::key::KeyCommand;
+use crate::cmd::storage::StorageCommand;
+use crate::cmd::tx::TxCommand;
 use crate::cmd::deploy::Deploy;
+use crate::cmd::gas_schedule::GasScheduleCommand;
 use crate::cmd::view::View;
 use crate::context::Context;
-use crate::natives::{all_natives, pontem_cost_table};
+use crate::errmap::{cached_project_error_mapping, merge};
+use crate::gas_schedule::load_cost_table;
+use crate::natives::{all_natives, without_extensions, NativeMocks};
 
 #[derive(Parser)]
 #[clap(
@@ -34,6 +43,19 @@ struct DoveOpt {
     pub cmd: DoveCommands,
 }
 
+/// Dove options of `dove test`.
+/// The command is parsed by move-cli as `package test`, so the options are added to it.
+#[derive(Args, Debug, Default)]
+struct TestArgs {
+    /// Native function mocks file (TOML or JSON).
+    /// Default: `mocks` in the package section of Move.toml.
+    #[clap(long = "mocks")]
+    pub mocks: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub execution: ExecutionArgs,
+}
+
 /// Move cli and dove commands.
 #[derive(clap::Subcommand)]
 pub enum DoveCommands {
@@ -93,6 +115,21 @@ pub enum DoveCommands {
         #[clap(flatten)]
         cmd: View,
     },
+    #[clap(about = "Transaction tools", subcommand, display_order = 20)]
+    Tx(TxCommand),
+    #[clap(
+        about = "Sandbox storage snapshots and balances",
+        subcommand,
+        display_order = 21
+    )]
+    Storage(StorageCommand),
+    #[clap(about = "Gas schedule tools", subcommand, display_order = 22)]
+    GasSchedule(GasScheduleCommand),
+    #[clap(about = "Time the natives and suggest their costs", display_order = 23)]
+    BenchNatives {
+        #[clap(flatten)]
+        cmd: BenchNatives,
+    },
 }
 
 fn preprocess_args(args: Vec<String>) -> Vec<String> {
@@ -121,7 +158,7 @@ pub fn execute(args: Vec<String>, cwd: PathBuf) -> Result<()> {
         check_dove_version(&minimal_version)?;
     }
     let args = preprocess_args(args);
-    let DoveOpt { move_args, cmd } = DoveOpt::parse_from(args);
+    let (DoveOpt { move_args, cmd }, test_args) = parse_args(args)?;
 
     // `dove clean`|`dove key` needs empty context and no preparation, so try it before other commands
     match cmd {
@@ -133,12 +170,53 @@ pub fn execute(args: Vec<String>, cwd: PathBuf) -> Result<()> {
         _ => (),
     };
 
-    let error_descriptions: ErrorMapping = bcs::from_bytes(ERROR_DESCRIPTIONS)?;
-    let native_functions = all_natives();
-    let cost_table = pontem_cost_table();
+    let mut error_descriptions: ErrorMapping = bcs::from_bytes(ERROR_DESCRIPTIONS)?;
+    let mut native_functions = all_natives();
+    let mut cost_table = load_cost_table(get_package_path(&cwd, "gas_schedule").as_deref())?;
+
+    // `dove gas-schedule`|`dove bench-natives` need the cost table only
+    match cmd {
+        DoveCommands::GasSchedule(mut cmd) => return cmd.apply(&cost_table),
+        DoveCommands::BenchNatives { mut cmd } => {
+            return cmd.apply(&cwd, native_functions, &cost_table)
+        }
+        _ => (),
+    };
 
     // process all diem commands before dove commands
     if let DoveCommands::DiemCommand(cmd) = cmd {
+        if let move_cli::Command::Package {
+            cmd: PackageCommand::UnitTest { .. },
+        } = &cmd
+        {
+            match cached_project_error_mapping(&cwd, &move_args.build_config) {
+                Ok(mapping) => merge(&mut error_descriptions, mapping),
+                Err(err) => println!(
+                    "Warning: failed to build the project error mapping\n{}",
+                    err
+                ),
+            }
+            let TestArgs { mocks, execution } = test_args;
+            if let Some(path) = &execution.gas_schedule {
+                cost_table = load_cost_table(Some(path))?;
+            }
+            let mocks = match mocks.or_else(|| get_package_path(&cwd, "mocks")) {
+                Some(path) => NativeMocks::load(&path)?,
+                None => NativeMocks::default(),
+            };
+            native_functions = mocks.apply(native_functions)?;
+            // each test starts with its own copy of the initial balances
+            let balances = execution.balances.for_tests(&cwd)?;
+            let chain_env = execution.chain_env.apply(ChainEnv::default());
+            set_extension_hook(Box::new(move |extensions| {
+                extensions.add(balances.clone());
+                extensions.add(chain_env);
+                extensions.add(mocks.clone());
+            }));
+        } else {
+            // the other move-cli sessions have no balances ledger and chain environment
+            native_functions = without_extensions(native_functions);
+        }
         return move_cli::run_cli(
             native_functions,
             &cost_table,
@@ -161,6 +239,8 @@ pub fn execute(args: Vec<String>, cwd: PathBuf) -> Result<()> {
         DoveCommands::Call { mut cmd } => cmd.apply(&mut ctx),
         DoveCommands::Deploy { mut cmd } => cmd.apply(&mut ctx),
         DoveCommands::View { mut cmd } => cmd.apply(&mut ctx),
+        DoveCommands::Tx(mut cmd) => cmd.apply(&mut ctx),
+        DoveCommands::Storage(mut cmd) => cmd.apply(&mut ctx),
         DoveCommands::Build
         | DoveCommands::Test
         | DoveCommands::Prove
@@ -168,12 +248,37 @@ pub fn execute(args: Vec<String>, cwd: PathBuf) -> Result<()> {
         | DoveCommands::Init => {
             unreachable!("Should never be reached, as all those commands are preprocessed into package-prefixed commands")
         }
-        DoveCommands::Clean { .. } | DoveCommands::DiemCommand(_) | DoveCommands::Key { .. } => {
+        DoveCommands::Clean { .. }
+        | DoveCommands::DiemCommand(_)
+        | DoveCommands::Key { .. }
+        | DoveCommands::GasSchedule(_)
+        | DoveCommands::BenchNatives { .. } => {
             unreachable!("Handled in the beginning")
         }
     }
 }
 
+/// Parses the command line. The options of `dove test` are added to move-cli `package test`.
+fn parse_args(args: Vec<String>) -> Result<(DoveOpt, TestArgs)> {
+    let mut command = DoveOpt::command();
+    if let Some(test) = command
+        .find_subcommand_mut("package")
+        .and_then(|package| package.find_subcommand_mut("test"))
+    {
+        *test = TestArgs::augment_args(std::mem::take(test));
+    }
+    let matches = command.get_matches_from(args);
+    let opt = DoveOpt::from_arg_matches(&matches)?;
+    let test_args = match matches
+        .subcommand_matches("package")
+        .and_then(|package| package.subcommand_matches("test"))
+    {
+        Some(test) => TestArgs::from_arg_matches(test)?,
+        None => TestArgs::default(),
+    };
+    Ok((opt, test_args))
+}
+
 /// Check if Dove version is suitable for this project
 fn check_dove_version(req_ver: &str) -> Result<(), Error> {
     let act_ver = env!("CARGO_PKG_VERSION");
@@ -226,6 +331,16 @@ fn get_minimal_dove_version(project_path: &Path) -> Option<String> {
         .and_then(|name| name.as_str().map(|t| t.to_string()))
 }
 
+/// Returns the path from the package section of Move.toml, relative to the project directory.
+fn get_package_path(project_path: &Path, key: &str) -> Option<PathBuf> {
+    let move_toml_content = std::fs::read_to_string(project_path.join("Move.toml")).ok()?;
+    let move_toml = toml::from_str::<toml::Value>(&move_toml_content).ok()?;
+    move_toml
+        .get("package")
+        .and_then(|pack| pack.get(key))
+        .and_then(|path| path.as_str().map(|path| project_path.join(path)))
+}
+
 #[cfg(test)]
 mod tests {
     use semver::Version;
@@ -0,0 +1,629 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use anyhow::{Error, Result};
+use clap::Parser;
+
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::file_format::Bytecode;
+use move_binary_format::file_format_common::instruction_key;
+use move_cli::sandbox::utils::get_gas_status;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::gas_schedule::CostTable;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, StructTag, CORE_CODE_ADDRESS};
+use move_core_types::resolver::{ModuleResolver, ResourceResolver};
+use move_package::BuildConfig;
+use move_package::compilation::compiled_package::CompiledUnit;
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_runtime::native_extensions::NativeContextExtensions;
+use move_vm_runtime::native_functions::NativeFunctionTable;
+
+use crate::balances::Balances;
+use crate::chain_env::ChainEnv;
+use crate::gas_schedule::{Cost, GasSchedule};
+use crate::natives::{
+    pontem_natives, native_cost_index, PontNativeCostIndex, SUBSTRATE_SIGNING_CONTEXT,
+};
+
+/// Module with the reference loops.
+const REFERENCE_MODULE: &str = "Reference";
+
+#[derive(Parser, Debug)]
+#[clap(about = "dove bench-natives [OPTIONS]\n
+    Times the Pontem natives and suggests their costs.
+    The time of a native call is normalized against the `x = x + 1` loop step
+    (CopyLoc, LdU64, Add, StLoc) and its cost in the current gas schedule.
+    The output file can be passed to `--gas-schedule`.
+
+    Examples:
+    $ dove bench-natives
+    $ dove bench-natives --iterations 100000 --output native_costs.toml
+")]
+pub struct BenchNatives {
+    /// Number of calls of each native.
+    #[clap(long, default_value = "10000")]
+    iterations: u64,
+
+    /// Suggested cost table (gas schedule TOML or JSON file).
+    #[clap(long, default_value = "native_costs.toml")]
+    output: PathBuf,
+}
+
+impl BenchNatives {
+    pub fn apply(
+        &mut self,
+        cwd: &Path,
+        natives: NativeFunctionTable,
+        cost_table: &CostTable,
+    ) -> Result<()> {
+        ensure!(
+            self.iterations > 0,
+            "The number of iterations must be positive"
+        );
+        let benches = benches();
+        for (_, module, function, _) in pontem_natives(CORE_CODE_ADDRESS) {
+            let covered = native_cost_index(module.as_str(), function.as_str())
+                .map(|index| benches.iter().any(|bench| bench.index == index))
+                .unwrap_or_default();
+            if !covered {
+                println!("Warning: no benchmark for {}::{}", module, function);
+            }
+        }
+
+        let storage = BenchStorage::build(&benches)?;
+        let vm = MoveVM::new(natives).map_err(|err| anyhow!("Failed to create VM: {:?}", err))?;
+        let run = |module: &str, function: &str, iterations: u64| -> Result<Duration> {
+            let mut extensions = NativeContextExtensions::default();
+            extensions.add(Balances::default());
+            extensions.add(ChainEnv::default());
+            let mut session = vm.new_session_with_extensions(&storage, extensions);
+            let mut gas_status = get_gas_status(cost_table, None)?;
+            let module_id = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new(module)?);
+            let function = Identifier::new(function)?;
+            let args = vec![bcs::to_bytes(&iterations)?];
+            let start = Instant::now();
+            session
+                .execute_script_function(&module_id, &function, vec![], args, &mut gas_status)
+                .map_err(|err| anyhow!("Failed to run {}::{}: {:?}", module, function, err))?;
+            Ok(start.elapsed())
+        };
+        // Time of the native call without the time of the arguments and the loop.
+        let measure = |module: &str, name: &str| -> Result<f64> {
+            let bench = format!("bench_{}", name);
+            let baseline = format!("baseline_{}", name);
+            // Warm up the loader cache.
+            run(module, &bench, 1)?;
+            run(module, &baseline, 1)?;
+            let bench = run(module, &bench, self.iterations)?;
+            let baseline = run(module, &baseline, self.iterations)?;
+            Ok(bench.saturating_sub(baseline).as_nanos() as f64 / self.iterations as f64)
+        };
+
+        let reference_ns = measure(REFERENCE_MODULE, "add")?;
+        ensure!(
+            reference_ns > 0.0,
+            "Failed to time the reference instructions. Increase the number of iterations"
+        );
+        let reference_gas = reference_gas(cost_table);
+        println!(
+            "Reference: {:.1} ns = {} gas (CopyLoc, LdU64, Add, StLoc)\n",
+            reference_ns, reference_gas
+        );
+
+        println!(
+            "{:<24}{:>12}{:>10}{:>12}",
+            "Native", "ns/call", "current", "suggested"
+        );
+        let mut natives = BTreeMap::new();
+        for bench in &benches {
+            let ns = measure(bench.module, &bench.fn_name())?;
+            let current = cost_table
+                .native_table
+                .get(bench.index as usize)
+                .map(Cost::from)
+                .ok_or_else(|| {
+                    anyhow!("Native {} is missing in the cost table", bench.index.name())
+                })?;
+            // Natives charge the total cost (instruction and memory gas)
+            // per unit of the argument size.
+            let suggested = (ns * reference_gas as f64 / reference_ns / bench.size.max(1) as f64)
+                .round()
+                .max(1.0) as u64;
+            let suggested = Cost {
+                instruction_gas: suggested.saturating_sub(current.memory_gas).max(1),
+                memory_gas: current.memory_gas,
+            };
+            println!(
+                "{:<24}{:>12.1}{:>10}{:>12}",
+                bench.index.name(),
+                ns,
+                current.instruction_gas + current.memory_gas,
+                suggested.instruction_gas + suggested.memory_gas
+            );
+            natives.insert(bench.index.name(), suggested);
+        }
+
+        let schedule = GasSchedule {
+            instructions: Default::default(),
+            natives,
+        };
+        let output = cwd.join(&self.output);
+        let content = match output.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::to_string_pretty(&schedule)?,
+            _ => toml::to_string_pretty(&schedule)?,
+        };
+        fs::write(&output, content)?;
+        println!("\nSuggested cost table: {}", output.display());
+        Ok(())
+    }
+}
+
+/// Gas of the reference loop step.
+fn reference_gas(cost_table: &CostTable) -> u64 {
+    [
+        Bytecode::CopyLoc(0),
+        Bytecode::LdU64(0),
+        Bytecode::Add,
+        Bytecode::StLoc(0),
+    ]
+    .iter()
+    .filter_map(|instr| {
+        cost_table
+            .instruction_table
+            .get(instruction_key(instr) as usize - 1)
+    })
+    .map(|cost| cost.total().get())
+    .sum()
+}
+
+/// Native call with representative arguments.
+struct NativeBench {
+    index: PontNativeCostIndex,
+    module: &'static str,
+    /// Function with the type arguments.
+    function: &'static str,
+    args: Vec<String>,
+    /// Number of the returned values.
+    returns: usize,
+    /// Argument size the native charges for.
+    size: usize,
+}
+
+impl NativeBench {
+    fn new(
+        index: PontNativeCostIndex,
+        module: &'static str,
+        function: &'static str,
+        args: &[&str],
+    ) -> NativeBench {
+        NativeBench {
+            index,
+            module,
+            function,
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            returns: 1,
+            size: 0,
+        }
+    }
+
+    fn returns(mut self, returns: usize) -> NativeBench {
+        self.returns = returns;
+        self
+    }
+
+    fn size(mut self, size: usize) -> NativeBench {
+        self.size = size;
+        self
+    }
+
+    fn fn_name(&self) -> String {
+        self.index.name().to_lowercase()
+    }
+
+    /// Bench and baseline functions. The baseline evaluates the same arguments without the call.
+    fn source(&self) -> String {
+        let call = format!("{}({})", self.function, self.args.join(", "));
+        let call = match self.returns {
+            0 => format!("{};", call),
+            1 => format!("let _ = {};", call),
+            n => format!("let ({}) = {};", vec!["_"; n].join(", "), call),
+        };
+        let baseline = self
+            .args
+            .iter()
+            .map(|arg| format!("let _ = {};", arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!(
+            concat!(
+                "    public(script) fun bench_{name}(n: u64) {{ let i = 0; while (i < n) {{ {call} i = i + 1; }}; }}\n",
+                "    public(script) fun baseline_{name}(n: u64) {{ let i = 0; while (i < n) {{ {baseline} i = i + 1; }}; }}\n",
+            ),
+            name = self.fn_name(),
+            call = call,
+            baseline = baseline,
+        )
+    }
+}
+
+fn benches() -> Vec<NativeBench> {
+    use crate::natives::PontNativeCostIndex as N;
+
+    let inputs = SignatureInputs::new();
+    vec![
+        NativeBench::new(N::U256_FROM_U8, "U256", "from_u8", &["255u8"]),
+        NativeBench::new(
+            N::U256_FROM_U64,
+            "U256",
+            "from_u64",
+            &["18446744073709551615"],
+        ),
+        NativeBench::new(
+            N::U256_FROM_U128,
+            "U256",
+            "from_u128",
+            &["340282366920938463463374607431768211455u128"],
+        ),
+        NativeBench::new(N::U256_AS_U8, "U256", "as_u8", &["small()"]),
+        NativeBench::new(N::U256_AS_U64, "U256", "as_u64", &["small()"]),
+        NativeBench::new(N::U256_AS_U128, "U256", "as_u128", &["large()"]),
+        NativeBench::new(N::U256_ADD, "U256", "add", &["large()", "large()"]),
+        NativeBench::new(N::U256_SUB, "U256", "sub", &["large()", "small()"]),
+        NativeBench::new(N::U256_MUL, "U256", "mul", &["large()", "large()"]),
+        NativeBench::new(N::U256_DIV, "U256", "div", &["large()", "small()"]),
+        NativeBench::new(N::U256_MOD, "U256", "mod", &["large()", "small()"]),
+        NativeBench::new(N::U256_POW, "U256", "pow", &["small()", "exp()"]).size(7),
+        NativeBench::new(N::U256_SHL, "U256", "shl", &["large()", "64u8"]),
+        NativeBench::new(N::U256_SHR, "U256", "shr", &["large()", "32u8"]),
+        NativeBench::new(N::U256_AND, "U256", "and", &["large()", "small()"]),
+        NativeBench::new(N::U256_OR, "U256", "or", &["large()", "small()"]),
+        NativeBench::new(N::U256_XOR, "U256", "xor", &["large()", "small()"]),
+        NativeBench::new(N::U256_COMPARE, "U256", "compare", &["large()", "small()"]),
+        NativeBench::new(N::U256_SQRT, "U256", "sqrt", &["large()"]),
+        NativeBench::new(N::U256_TO_BYTES, "U256", "to_bytes", &["large()"]),
+        NativeBench::new(N::U256_FROM_BYTES, "U256", "from_bytes", &["LARGE"]).size(32),
+        NativeBench::new(N::CREATE_SIGNER, "PontAccount", "create_signer", &["@0x1"]),
+        NativeBench::new(
+            N::DESTROY_SIGNER,
+            "PontAccount",
+            "destroy_signer",
+            &["create_signer(@0x1)"],
+        )
+        .returns(0),
+        NativeBench::new(N::PONT_BALANCE, "PontAccount", "native_balance", &["@0x1"]),
+        NativeBench::new(
+            N::PONT_DEPOSIT,
+            "PontAccount",
+            "native_deposit",
+            &["@0x1", "0"],
+        )
+        .returns(0),
+        NativeBench::new(
+            N::PONT_WITHDRAW,
+            "PontAccount",
+            "native_withdraw",
+            &["@0x1", "0"],
+        )
+        .returns(0),
+        NativeBench::new(
+            N::PONT_TRANSFER,
+            "PontAccount",
+            "native_transfer",
+            &["@0x1", "@0x2", "0"],
+        )
+        .returns(0),
+        NativeBench::new(
+            N::ED25519_VALIDATE_KEY,
+            "Signature",
+            "ed25519_validate_pubkey",
+            &[&inputs.ed25519_public_key],
+        )
+        .size(32),
+        NativeBench::new(
+            N::ED25519_VERIFY,
+            "Signature",
+            "ed25519_verify",
+            &[
+                &inputs.ed25519_signature,
+                &inputs.ed25519_public_key,
+                MESSAGE,
+            ],
+        )
+        .size(MESSAGE_LEN),
+        NativeBench::new(
+            N::SR25519_VERIFY,
+            "Signature",
+            "sr25519_verify",
+            &[
+                &inputs.sr25519_signature,
+                &inputs.sr25519_public_key,
+                MESSAGE,
+            ],
+        )
+        .size(MESSAGE_LEN),
+        NativeBench::new(
+            N::SECP256K1_VERIFY,
+            "Signature",
+            "secp256k1_verify",
+            &[
+                &inputs.secp256k1_signature,
+                &inputs.secp256k1_public_key,
+                &inputs.hash,
+            ],
+        )
+        .size(32),
+        NativeBench::new(
+            N::SECP256K1_ECRECOVER,
+            "Signature",
+            "secp256k1_ecrecover",
+            &[&inputs.hash, &inputs.secp256k1_recoverable_signature],
+        )
+        .returns(2)
+        .size(32),
+        NativeBench::new(N::BLAKE2B_256, "PontHash", "blake2b_256", &[MESSAGE]).size(MESSAGE_LEN),
+        NativeBench::new(N::KECCAK_256, "PontHash", "keccak_256", &[MESSAGE]).size(MESSAGE_LEN),
+        NativeBench::new(N::TYPE_INFO, "Reflect", "type_info<TypeInfo>", &[]),
+        NativeBench::new(N::TYPE_NAME, "Reflect", "type_name<vector<TypeInfo>>", &[]),
+        NativeBench::new(N::BLOCK_HEIGHT, "Block", "get_current_block_height", &[]),
+        NativeBench::new(N::TIMESTAMP, "Timestamp", "now_microseconds", &[]),
+    ]
+}
+
+/// 128 bytes message.
+const MESSAGE: &str = "b\"The quick brown fox jumps over the lazy dog. The quick brown fox jumps over the lazy dog. The quick brown fox jumps over the laz\"";
+const MESSAGE_LEN: usize = 128;
+
+/// Valid signatures made at runtime.
+struct SignatureInputs {
+    ed25519_signature: String,
+    ed25519_public_key: String,
+    sr25519_signature: String,
+    sr25519_public_key: String,
+    hash: String,
+    secp256k1_signature: String,
+    secp256k1_recoverable_signature: String,
+    secp256k1_public_key: String,
+}
+
+impl SignatureInputs {
+    fn new() -> SignatureInputs {
+        let message = &MESSAGE.as_bytes()[2..MESSAGE.len() - 1];
+        let ed25519_secret =
+            ed25519_dalek::SecretKey::from_bytes(&[7; 32]).expect("Valid secret key");
+        let ed25519_public = ed25519_dalek::PublicKey::from(&ed25519_secret);
+        let ed25519_signature = ed25519_dalek::ExpandedSecretKey::from(&ed25519_secret)
+            .sign(message, &ed25519_public);
+
+        let keypair = schnorrkel::Keypair::generate_with(rand::rngs::OsRng);
+        let sr25519_signature = keypair.sign_simple(SUBSTRATE_SIGNING_CONTEXT, message);
+
+        let secret = libsecp256k1::SecretKey::parse(&[7; 32]).expect("Valid secret key");
+        let hash = [42; 32];
+        let (signature, recovery_id) =
+            libsecp256k1::sign(&libsecp256k1::Message::parse(&hash), &secret);
+        let mut recoverable = signature.serialize().to_vec();
+        recoverable.push(recovery_id.serialize());
+
+        SignatureInputs {
+            ed25519_signature: hex_literal(&ed25519_signature.to_bytes()),
+            ed25519_public_key: hex_literal(ed25519_public.as_bytes()),
+            sr25519_signature: hex_literal(&sr25519_signature.to_bytes()),
+            sr25519_public_key: hex_literal(&keypair.public.to_bytes()),
+            hash: hex_literal(&hash),
+            secp256k1_signature: hex_literal(&signature.serialize()),
+            secp256k1_recoverable_signature: hex_literal(&recoverable),
+            secp256k1_public_key: hex_literal(
+                &libsecp256k1::PublicKey::from_secret_key(&secret).serialize_compressed(),
+            ),
+        }
+    }
+}
+
+fn hex_literal(bytes: &[u8]) -> String {
+    format!("x\"{}\"", hex::encode(bytes))
+}
+
+/// Declarations of the natives and the helpers of the benchmarks.
+fn module_header(module: &str) -> &'static str {
+    match module {
+        "U256" => {
+            r#"
+    struct U256 has copy, drop { v: vector<u8> }
+    const LARGE: vector<u8> = x"ffffffffffffffffffffffffffffffff00000000000000000000000000000000";
+    fun large(): U256 { U256 { v: LARGE } }
+    fun small(): U256 { U256 { v: x"0300000000000000000000000000000000000000000000000000000000000000" } }
+    fun exp(): U256 { U256 { v: x"6400000000000000000000000000000000000000000000000000000000000000" } }
+    native fun from_u8(v: u8): U256;
+    native fun from_u64(v: u64): U256;
+    native fun from_u128(v: u128): U256;
+    native fun as_u8(v: U256): u8;
+    native fun as_u64(v: U256): u64;
+    native fun as_u128(v: U256): u128;
+    native fun add(l: U256, r: U256): U256;
+    native fun sub(l: U256, r: U256): U256;
+    native fun mul(l: U256, r: U256): U256;
+    native fun div(l: U256, r: U256): U256;
+    native fun mod(l: U256, r: U256): U256;
+    native fun pow(base: U256, exp: U256): U256;
+    native fun shl(v: U256, shift: u8): U256;
+    native fun shr(v: U256, shift: u8): U256;
+    native fun and(l: U256, r: U256): U256;
+    native fun or(l: U256, r: U256): U256;
+    native fun xor(l: U256, r: U256): U256;
+    native fun compare(l: U256, r: U256): u8;
+    native fun sqrt(v: U256): U256;
+    native fun to_bytes(v: U256): vector<u8>;
+    native fun from_bytes(v: vector<u8>): U256;
+"#
+        }
+        "PontAccount" => {
+            r#"
+    native fun create_signer(addr: address): signer;
+    native fun destroy_signer(sig: signer);
+    native fun native_balance(addr: address): u128;
+    native fun native_deposit(addr: address, amount: u128);
+    native fun native_withdraw(addr: address, amount: u128);
+    native fun native_transfer(from: address, to: address, amount: u128);
+"#
+        }
+        "Signature" => {
+            r#"
+    native fun ed25519_validate_pubkey(public_key: vector<u8>): bool;
+    native fun ed25519_verify(signature: vector<u8>, public_key: vector<u8>, message: vector<u8>): bool;
+    native fun sr25519_verify(signature: vector<u8>, public_key: vector<u8>, message: vector<u8>): bool;
+    native fun secp256k1_verify(signature: vector<u8>, public_key: vector<u8>, hash: vector<u8>): bool;
+    native fun secp256k1_ecrecover(hash: vector<u8>, signature: vector<u8>): (vector<u8>, bool);
+"#
+        }
+        "PontHash" => {
+            r#"
+    native fun blake2b_256(data: vector<u8>): vector<u8>;
+    native fun keccak_256(data: vector<u8>): vector<u8>;
+"#
+        }
+        "Reflect" => {
+            r#"
+    struct TypeInfo has copy, drop { account_address: address, module_name: vector<u8>, struct_name: vector<u8> }
+    native fun type_info<T>(): TypeInfo;
+    native fun type_name<T>(): vector<u8>;
+"#
+        }
+        "Block" => {
+            r#"
+    native fun get_current_block_height(): u64;
+"#
+        }
+        "Timestamp" => {
+            r#"
+    native fun now_microseconds(): u64;
+"#
+        }
+        REFERENCE_MODULE => {
+            r#"
+    public(script) fun bench_add(n: u64) { let i = 0; let x = 0; while (i < n) { x = x + 1; i = i + 1; }; let _ = x; }
+    public(script) fun baseline_add(n: u64) { let i = 0; let x = 0; while (i < n) { i = i + 1; }; let _ = x; }
+"#
+        }
+        _ => "",
+    }
+}
+
+/// Compiled benchmark modules.
+struct BenchStorage {
+    modules: HashMap<ModuleId, Vec<u8>>,
+}
+
+impl BenchStorage {
+    /// Generates and compiles the benchmark package in the temp directory.
+    fn build(benches: &[NativeBench]) -> Result<BenchStorage, Error> {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("dove_bench_natives")
+            .tempdir()?;
+        let package_dir = temp_dir.path();
+        let sources_dir = package_dir.join("sources");
+        fs::create_dir_all(&sources_dir)?;
+        fs::write(
+            package_dir.join("Move.toml"),
+            "[package]\nname = \"BenchNatives\"\nversion = \"0.0.0\"\n",
+        )?;
+
+        let mut modules = benches
+            .iter()
+            .map(|bench| bench.module)
+            .collect::<BTreeSet<_>>();
+        modules.insert(REFERENCE_MODULE);
+        for module in modules {
+            let functions = benches
+                .iter()
+                .filter(|bench| bench.module == module)
+                .map(|bench| bench.source())
+                .collect::<String>();
+            fs::write(
+                sources_dir.join(format!("{}.move", module)),
+                format!(
+                    "module 0x1::{} {{{}\n{}}}\n",
+                    module,
+                    module_header(module),
+                    functions
+                ),
+            )?;
+        }
+
+        let build_config = BuildConfig {
+            dev_mode: false,
+            test_mode: false,
+            generate_docs: false,
+            generate_abis: false,
+            install_dir: None,
+            force_recompilation: true,
+            additional_named_addresses: Default::default(),
+            language_flavor: None,
+        };
+        let package = build_config.compile_package(package_dir, &mut std::io::sink())?;
+        let modules = package
+            .root_compiled_units
+            .iter()
+            .filter_map(|unit| match &unit.unit {
+                CompiledUnit::Module(module) => Some(&module.module),
+                CompiledUnit::Script(_) => None,
+            })
+            .map(|module| {
+                let mut bytes = vec![];
+                module.serialize(&mut bytes)?;
+                Ok((module.self_id(), bytes))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+        temp_dir.close()?;
+        Ok(BenchStorage { modules })
+    }
+}
+
+impl ModuleResolver for BenchStorage {
+    type Error = Error;
+
+    fn get_module(&self, module_id: &ModuleId) -> Result<Option<Vec<u8>>> {
+        Ok(self.modules.get(module_id).cloned())
+    }
+}
+
+impl ResourceResolver for BenchStorage {
+    type Error = Error;
+
+    fn get_resource(
+        &self,
+        _address: &AccountAddress,
+        _tag: &StructTag,
+    ) -> Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::language_storage::CORE_CODE_ADDRESS;
+    use crate::natives::{native_cost_index, pontem_natives};
+    use super::{benches, module_header};
+
+    #[test]
+    fn test_benches_cover_pontem_natives() {
+        let benches = benches();
+        for (_, module, function, _) in pontem_natives(CORE_CODE_ADDRESS) {
+            let index = native_cost_index(module.as_str(), function.as_str()).unwrap();
+            assert!(
+                benches.iter().any(|bench| bench.index == index),
+                "{}::{}",
+                module,
+                function
+            );
+        }
+        for bench in &benches {
+            let function = bench.function.split('<').next().unwrap();
+            assert!(module_header(bench.module).contains(&format!("native fun {}", function)));
+            assert!(bench
+                .source()
+                .contains(&format!("bench_{}", bench.fn_name())));
+        }
+    }
+}
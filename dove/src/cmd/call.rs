@@ -8,10 +8,12 @@ use anyhow::{Error, Result};
 use lang::bytecode::accessor::BytecodeRef;
 use crate::cmd::deploy::run_dove_package_build;
 use crate::context::Context;
+use crate::errmap::explain_node_error;
 use crate::call::cmd::CallDeclarationCmd;
 use crate::call::fn_call::Config;
-use crate::call::make_transaction;
-use crate::call::model::{EnrichedTransaction, Transaction};
+use crate::call::interactive::prompt_call_args;
+use crate::call::{make_batch, make_transaction};
+use crate::call::model::{EnrichedTransaction, TxMetadata};
 use crate::publish::{NodeAccessParams, Publish};
 
 #[derive(Parser, Debug)]
@@ -23,6 +25,10 @@ use crate::publish::{NodeAccessParams, Publish};
     $ dove call 'script_name()' --account WALLET_KEY --gas 300
     $ dove call 'script_name()' --secret --url https://127.0.0.1:9933 --gas 400
     $ dove call 'script_name()' --account //Alice --gas 300
+    $ dove call 'script_name()' --chain-id pontem-testnet --expiration-block 100000 --memo 'payment'
+    $ dove call script_name     # prompts for the type parameters and arguments in a terminal
+    $ dove call 'first()' --then 'second(1)' --then '0x1::Module::third(true)'
+    $ dove call @release.calls --account //Alice --gas 300
 ")]
 pub struct ExecuteTransaction {
     #[clap(flatten)]
@@ -30,17 +36,51 @@ pub struct ExecuteTransaction {
 
     #[clap(flatten)]
     request: NodeAccessParams,
+
+    /// Chain or network id the transaction is intended for.
+    /// If set, the transaction is created in version 2.
+    #[clap(long = "chain-id")]
+    chain_id: Option<String>,
+
+    /// The block after which the transaction is no longer valid.
+    /// If set, the transaction is created in version 2.
+    #[clap(long = "expiration-block")]
+    expiration_block: Option<u64>,
+
+    /// Free-form transaction memo.
+    /// If set, the transaction is created in version 2.
+    #[clap(long = "memo")]
+    memo: Option<String>,
+
+    /// Call declarations executed after the main call. All calls are stored in one batch.
+    /// The batch is published transaction by transaction and stops at the first failed one.
+    #[clap(long = "then", multiple_occurrences = true)]
+    then: Vec<String>,
 }
 
 impl ExecuteTransaction {
     pub fn apply(&mut self, ctx: &mut Context) -> Result<()> {
         run_dove_package_build(ctx)?;
-        let tx = make_transaction(ctx, self.call.take(), Config::for_tx())?;
+        let cfg = self.config();
+        let tx = match self.batch_calls()? {
+            Some(calls) => make_batch(ctx, calls, cfg)?,
+            None => {
+                prompt_call_args(ctx, &mut self.call)?;
+                make_transaction(ctx, self.call.take(), cfg)?
+            }
+        };
         let path_transaction = match tx {
             EnrichedTransaction::Local { .. } => unreachable!(),
             EnrichedTransaction::Global { bi, tx, name } => {
-                store_transaction(ctx, &name, bi.bytecode_ref(), tx)?
+                store_transaction(ctx, &name, bi.bytecode_ref(), &bcs::to_bytes(&tx)?, "mvt")?
             }
+            EnrichedTransaction::Batch { bi, batch, name } => store_transaction(
+                ctx,
+                &name,
+                bi.bytecode_ref(),
+                &bcs::to_bytes(&batch)?,
+                "mvb",
+            )?,
         };
 
         if !self.request.need_to_publish() {
@@ -49,17 +89,73 @@ impl ExecuteTransaction {
 
         Publish::try_from((&self.request, path_transaction))?
             .apply()
+            .map_err(|err| explain_node_error(&ctx.error_descriptions, err))
             .map(|hash| {
                 println!("Hash: {}", hash);
             })
     }
+
+    /// Returns the batch calls if more than one call is declared.
+    /// The calls are taken from the `@FILE` list file (one call per line) and the `--then` option.
+    fn batch_calls(&mut self) -> Result<Option<Vec<CallDeclarationCmd>>> {
+        let mut calls = match self.call.call.strip_prefix('@') {
+            Some(path) => {
+                ensure!(
+                    self.call.type_parameters.is_none()
+                        && self.call.params.is_none()
+                        && self.call.args_file.is_none(),
+                    "Type parameters and arguments must be declared in the calls of the list file."
+                );
+                read_call_list(Path::new(path))?
+                    .into_iter()
+                    .map(|call| CallDeclarationCmd::new(call, self.call.package.clone()))
+                    .collect()
+            }
+            None if self.then.is_empty() => return Ok(None),
+            None => vec![self.call.take()],
+        };
+        calls.extend(
+            self.then
+                .drain(..)
+                .map(|call| CallDeclarationCmd::new(call, self.call.package.clone())),
+        );
+        Ok(Some(calls))
+    }
+
+    fn config(&mut self) -> Config {
+        let cfg = Config::for_tx().with_chain_url(self.request.api_url());
+        if self.chain_id.is_none() && self.expiration_block.is_none() && self.memo.is_none() {
+            return cfg;
+        }
+        cfg.with_metadata(TxMetadata {
+            chain_id: self.chain_id.take(),
+            expiration_block: self.expiration_block.take(),
+            memo: self.memo.take(),
+        })
+    }
+}
+
+/// Reads call declarations from the list file.
+/// Each non-empty line is a call declaration. Lines starting with '#' are ignored.
+fn read_call_list(path: &Path) -> Result<Vec<String>, Error> {
+    let content = fs::read_to_string(path)
+        .map_err(|err| anyhow!("Failed to read call list {:?}: {}", path, err))?;
+    let calls = content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_owned())
+        .collect::<Vec<_>>();
+    ensure!(!calls.is_empty(), "Call list {:?} is empty.", path);
+    Ok(calls)
 }
 
 fn store_transaction(
     ctx: &Context,
     name: &str,
     rf: &BytecodeRef,
-    tx: Transaction,
+    bytes: &[u8],
+    ext: &str,
 ) -> Result<PathBuf, Error> {
     let tx_dir = ctx.tx_output_path(get_package_from_path(&rf.0));
     if !tx_dir.exists() {
@@ -67,8 +163,8 @@ fn store_transaction(
     }
 
     let mut tx_file = tx_dir.join(name);
-    if !name.to_lowercase().ends_with(".mvt") {
-        tx_file.set_extension("mvt");
+    if !name.to_lowercase().ends_with(&format!(".{}", ext)) {
+        tx_file.set_extension(ext);
     }
 
     if tx_file.exists() {
@@ -76,12 +172,12 @@ fn store_transaction(
     }
     // println!("Store transaction: {:?}", tx_file);
     // println!("Print  transaction {:?} hex bytes: 0x{}", tx_file, hex::encode(bcs::to_bytes(&tx)?));
-    fs::write(&tx_file, bcs::to_bytes(&tx)?)?;
+    fs::write(&tx_file, bytes)?;
 
     Ok(tx_file)
 }
 
-fn get_package_from_path<A: AsRef<Path>>(path: A) -> Option<String> {
+pub(crate) fn get_package_from_path<A: AsRef<Path>>(path: A) -> Option<String> {
     let path: &Path = path.as_ref();
     path.parent()
         .and_then(|p| p.parent())
@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use anyhow::{Error, Result};
 use clap::Parser;
 
+use crate::cmd::storage::snapshot_dir;
 use crate::context::Context;
 use crate::dot_move_folder;
 
@@ -14,7 +15,9 @@ pub struct Clean {
     //      PROJECT_DIR/storage
     //      PROJECT_DIR/build/mv_interfaces
     //      PROJECT_DIR/build/package
-    // [all] Clear all:
+    // [state --snapshot NAME] Clear only the named storage snapshot:
+    //      PROJECT_DIR/snapshots/NAME
+    // [all] Clear all except the storage snapshots:
     //      PROJECT_DIR/storage
     //      PROJECT_DIR/build
     #[clap(help = "Type of cleaning. [default=all]\n\
@@ -22,6 +25,12 @@ pub struct Clean {
                         all - Clear all.")]
     clear_type: Option<ClearType>,
 
+    #[clap(
+        help = "Clear only the named storage snapshot. Implies the `state` type of cleaning.",
+        long
+    )]
+    snapshot: Option<String>,
+
     // deleting folders:
     //      PROJECT_DIR/storage
     //      PROJECT_DIR/build
@@ -32,11 +41,23 @@ pub struct Clean {
 
 impl Clean {
     pub fn apply(&mut self, project_root_dir: &Path) {
-        let clear_type = self.clear_type.unwrap_or_default();
+        let clear_type = match (self.clear_type, &self.snapshot) {
+            (Some(clear_type), _) => clear_type,
+            (None, Some(_)) => ClearType::State,
+            (None, None) => ClearType::default(),
+        };
 
-        let mut folders = match clear_type {
+        let mut folders = match (clear_type, self.snapshot.as_deref()) {
+            // Clear only the named storage snapshot.
+            (ClearType::State, Some(name)) => match snapshot_dir(project_root_dir, name) {
+                Ok(path) => vec![path],
+                Err(err) => {
+                    println!("Warning: {}", err);
+                    vec![]
+                }
+            },
             // Clear only the executor state.
-            ClearType::State => {
+            (ClearType::State, None) => {
                 vec![
                     project_root_dir.join("storage"),
                     project_root_dir.join("build").join("mv_interfaces"),
@@ -44,7 +65,7 @@ impl Clean {
                 ]
             }
             // Clear all.
-            ClearType::All => {
+            (ClearType::All, _) => {
                 vec![
                     project_root_dir.join("storage"),
                     project_root_dir.join("build"),
@@ -19,6 +19,7 @@ use move_cli::run_cli;
 use move_core_types::language_storage::ModuleId;
 
 use crate::context::Context;
+use crate::errmap::{explain_node_error, update_project_error_mapping};
 use crate::publish::{NodeAccessParams, Publish};
 
 #[derive(Parser, Debug)]
@@ -122,6 +123,7 @@ impl Deploy {
 
         Publish::try_from((&self.request, file_path))?
             .apply()
+            .map_err(|err| explain_node_error(&ctx.error_descriptions, err))
             .map(|hash| {
                 println!("Hash: {}", hash);
             })
@@ -213,7 +215,32 @@ pub fn run_dove_package_build(ctx: &mut Context) -> Result<()> {
         &ctx.error_descriptions,
         &ctx.move_args,
         &build_cmd,
-    )
+    )?;
+
+    if let Err(err) = update_project_error_mapping(ctx) {
+        println!(
+            "Warning: failed to build the project error mapping\n{}",
+            err
+        );
+    }
+    Ok(())
+}
+
+/// Builds the package like `run_dove_package_build`, but writes the build output to stderr.
+/// Used by the commands printing machine-readable output.
+pub fn run_dove_package_build_to_stderr(ctx: &mut Context) -> Result<()> {
+    ctx.move_args
+        .build_config
+        .clone()
+        .compile_package(&ctx.project_root_dir, &mut std::io::stderr())?;
+
+    if let Err(err) = update_project_error_mapping(ctx) {
+        eprintln!(
+            "Warning: failed to build the project error mapping\n{}",
+            err
+        );
+    }
+    Ok(())
 }
 
 #[inline]
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use clap::Parser;
+use move_core_types::gas_schedule::CostTable;
+
+use crate::gas_schedule::GasSchedule;
+
+/// Gas schedule tools
+#[derive(Debug, Parser)]
+pub enum GasScheduleCommand {
+    /// Print the current gas schedule
+    #[clap(name = "print")]
+    Print {
+        /// Print the gas schedule in JSON format. TOML is used by default
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+impl GasScheduleCommand {
+    pub fn apply(&mut self, cost_table: &CostTable) -> Result<()> {
+        match self {
+            GasScheduleCommand::Print { json } => {
+                let schedule = GasSchedule::from_cost_table(cost_table);
+                if *json {
+                    println!("{}", serde_json::to_string_pretty(&schedule)?);
+                } else {
+                    print!("{}", toml::to_string_pretty(&schedule)?);
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -1,12 +1,20 @@
+/// Native gas calibration benchmark.
+pub mod bench_natives;
 /// Create transaction.
 pub mod call;
 /// Project dependencies loader.
 pub mod clean;
 /// Project builder.
 pub mod deploy;
+/// Gas schedule tools.
+pub mod gas_schedule;
 /// Manage wallet keys
 pub mod key;
 /// Script executor.
 pub mod run;
+/// Sandbox storage snapshots and balances.
+pub mod storage;
+/// Transaction tools.
+pub mod tx;
 /// resource-viewer
 pub mod view;
@@ -1,24 +1,38 @@
-use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use clap::Parser;
-use anyhow::{Error, Result};
-use move_bytecode_source_map::source_map::SourceMap;
-use lang::bytecode::info::BytecodeInfo;
-use move_cli::{DEFAULT_STORAGE_DIR, Move, run_cli};
-use move_cli::sandbox::cli::SandboxCommand;
-use move_cli::Command;
-use move_command_line_common::files::FileHash;
-
-use move_package::BuildConfig;
-use move_package::compilation::package_layout::CompiledPackageLayout;
-use crate::cmd::deploy::run_dove_package_build;
+use clap::{Args, Parser};
+use url::Url;
+use anyhow::Result;
+use move_cli::DEFAULT_STORAGE_DIR;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::TypeTag;
+
+use crate::balances::{Balances, BalancesArgs};
+use crate::chain_env::{ChainEnv, ChainEnvArgs};
+use crate::cmd::deploy::{run_dove_package_build, run_dove_package_build_to_stderr};
 use crate::context::Context;
-
 use crate::call::cmd::CallDeclarationCmd;
-use crate::call::fn_call::Config;
+use crate::call::fn_call::{diem_root_address, parse_signer, Config};
 use crate::call::make_transaction;
-use crate::call::model::EnrichedTransaction;
+use crate::call::model::{Call, EnrichedTransaction, Signer, Transaction};
+use crate::executor::{execute, ExecutionParams, ForkParams};
+use crate::gas_profile::{run_traced, trace_path, GasProfileFormat};
+use crate::gas_schedule::load_cost_table;
+
+/// Options of the local execution: `dove run` and `dove test`.
+#[derive(Args, Debug, Default)]
+pub struct ExecutionArgs {
+    /// Gas schedule file (TOML or JSON) overriding the instruction and native costs.
+    /// Default: `gas_schedule` in the package section of Move.toml.
+    #[clap(long = "gas-schedule")]
+    pub gas_schedule: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub chain_env: ChainEnvArgs,
+
+    #[clap(flatten)]
+    pub balances: BalancesArgs,
+}
 
 #[derive(Parser, Debug)]
 #[clap(about = "dove run [call] [OPTIONS]\n
@@ -29,6 +43,15 @@ use crate::call::model::EnrichedTransaction;
     $ dove run 'Module::function()'
     $ dove run '0x1::Module::function()'
     $ dove run '0x1::Module::function' --args [10,10] true ALIAS_ADDRESSES SS58_ADDRESS 100 0x1 --type '0x01::Dfinance::USD'
+    $ dove run 'script_name([[1,2],[3]], [0x1, 0x2], \"hello\")'
+    $ dove run 'script_name()' --storage ./scenario_storage
+    $ dove run 'script_name()' --gas-profile=folded
+    $ dove run 'script_name()' --json
+    $ dove run build/for_tests/transaction/main.mvt --signer 0x1
+    $ dove run 'script_name()' --block-height 100 --timestamp 1650000000000000
+    $ dove run 'script_name()' --advance-blocks 10
+    $ dove run 'script_name()' --balance Demo=1000 --balance 0x3=500
+    $ dove run 'script_name()' --fork-url http://127.0.0.1:9933 --height 100 --storage ./fork_storage
 ")]
 pub struct Run {
     #[clap(flatten)]
@@ -39,140 +62,187 @@ pub struct Run {
     #[clap(long = "dry-run")]
     dry_run: bool,
 
+    /// Print one JSON document: the emitted events, the write-set, the balances
+    /// and the gas profile. The build output is written to stderr.
+    #[clap(long)]
+    json: bool,
+
+    /// Signers to replace the placeholders of the transaction file (.mvt).
+    #[clap(long = "signer", multiple_occurrences = true)]
+    signers: Vec<String>,
+
+    /// Sandbox storage directory.
+    #[clap(long = "storage", default_value = DEFAULT_STORAGE_DIR)]
+    storage_dir: PathBuf,
+
     #[clap(long = "gas_budget", short = 'g', default_value = "1000000000")]
     gas_budget: u64,
+
+    /// Print the gas consumption per function frame, instruction class and native.
+    /// Formats: text, json, folded (for flamegraph tools).
+    /// Requires dove built with the `gas-profile` feature.
+    #[clap(
+        long = "gas-profile",
+        require_equals = true,
+        min_values = 0,
+        default_missing_value = "text"
+    )]
+    gas_profile: Option<GasProfileFormat>,
+
+    /// The url of the node API to fork the state from. HTTP or HTTPS only.
+    /// Modules and resources missing in the sandbox storage are loaded from the chain,
+    /// the changes are written to the sandbox storage only.
+    #[clap(long = "fork-url")]
+    fork_url: Option<Url>,
+
+    /// Block number to fork the state at. The latest block is used by default.
+    #[clap(long = "height", requires = "fork_url")]
+    height: Option<String>,
+
+    #[clap(flatten)]
+    execution: ExecutionArgs,
 }
 
 impl Run {
     pub fn apply(&mut self, ctx: &mut Context) -> Result<()> {
-        run_dove_package_build(ctx)?;
-        let tx = make_transaction(ctx, self.call.take(), Config::for_run())?;
-        match tx {
-            EnrichedTransaction::Local {
-                bi,
-                args,
-                type_tag,
-                func_name,
-                signers,
-            } => {
-                let script_file = resolve_script_name(&bi)?;
-
-                let args = args
-                    .into_iter()
-                    .map(|arg| arg.try_into())
-                    .collect::<Result<_, Error>>()?;
-                let cmd = SandboxCommand::Run {
-                    script_file,
-                    script_name: func_name,
-                    signers: signers.iter().map(|addr| addr.to_hex_literal()).collect(),
-                    args,
-                    type_args: type_tag,
-                    gas_budget: self.gas_budget,
-                    dry_run: self.dry_run,
-                };
-
-                let named_addresses = ctx
-                    .address_declarations()
-                    .into_iter()
-                    .filter_map(|(k, v)| v.map(|v| (k, v)))
-                    .map(|(k, v)| (k.to_string(), v))
-                    .collect();
-
-                let move_args = Move {
-                    package_path: ctx.project_root_dir.clone(),
-                    verbose: ctx.move_args.verbose,
-                    build_config: BuildConfig {
-                        dev_mode: true,
-                        test_mode: true,
-                        generate_docs: false,
-                        generate_abis: false,
-                        install_dir: None,
-                        force_recompilation: false,
-                        additional_named_addresses: named_addresses,
-                        language_flavor: None,
-                    },
-                };
-                run_cli(
-                    ctx.native_functions.clone(),
-                    &ctx.cost_table,
-                    &ctx.error_descriptions,
-                    &move_args,
-                    &Command::Sandbox {
-                        storage_dir: PathBuf::from(DEFAULT_STORAGE_DIR),
-                        cmd,
-                    },
-                )
+        if self.gas_profile.is_some() {
+            ensure!(
+                cfg!(feature = "gas-profile"),
+                "dove is built without the gas profiler. Rebuild it with the 'gas-profile' feature"
+            );
+            // the traced child process builds and executes, the parent only waits for it
+            if let Some(status) = run_traced(&trace_path(&ctx.project_root_dir))? {
+                ensure!(status.success(), "dove run failed: {}", status);
+                return Ok(());
             }
-            EnrichedTransaction::Global { .. } => unreachable!(),
         }
-    }
-}
 
-fn resolve_script_name(bi: &BytecodeInfo) -> Result<PathBuf> {
-    let path = PathBuf::from(&bi.bytecode_ref().0);
-    let name = path
-        .file_name()
-        .map(|name| name.to_string_lossy().to_string())
-        .ok_or_else(|| anyhow!("Failed to get file name:{:?}", path))?;
-    let package = path
-        .parent()
-        .and_then(|p| p.parent())
-        .ok_or_else(|| anyhow!("Failed to get package dir:{:?}", path))?;
-    let mut source_map = package
-        .join(CompiledPackageLayout::SourceMaps.path())
-        .join(name);
-    source_map.set_extension("mvsm");
-    let source_map: SourceMap = bcs::from_bytes(&fs::read(source_map)?)?;
-
-    let project_path = path
-        .parent()
-        .and_then(|parent| parent.parent())
-        .and_then(|parent| parent.parent())
-        .and_then(|parent| parent.parent())
-        .ok_or_else(|| anyhow!("Failed to get project dir: {:?}", path))?;
-
-    find_loc(project_path, &source_map)
-}
+        if self.json {
+            run_dove_package_build_to_stderr(ctx)?;
+        } else {
+            run_dove_package_build(ctx)?;
+        }
+        let (call, signers, args, type_tag) = if is_tx_file(&self.call.call) {
+            self.load_transaction(ctx)?
+        } else {
+            ensure!(
+                self.signers.is_empty(),
+                "Signers can be passed only to the transaction file. Use the call arguments instead."
+            );
+            let cfg = Config::for_run().with_chain_url(self.fork_url.clone());
+            match make_transaction(ctx, self.call.take(), cfg)? {
+                EnrichedTransaction::Local {
+                    call,
+                    args,
+                    type_tag,
+                    signers,
+                    ..
+                } => (call, signers, args, type_tag),
+                EnrichedTransaction::Global { .. } | EnrichedTransaction::Batch { .. } => {
+                    unreachable!()
+                }
+            }
+        };
 
-fn find_loc(project_path: &Path, source_map: &SourceMap) -> Result<PathBuf> {
-    let map = find_move_files_in_project(project_path);
-    let hash = source_map.definition_location.file_hash();
+        if let Some(path) = &self.execution.gas_schedule {
+            ctx.cost_table = load_cost_table(Some(path))?;
+        }
+        let storage_dir = ctx.project_root_dir.join(&self.storage_dir);
+        let chain_env = self
+            .execution
+            .chain_env
+            .apply(ChainEnv::load(&storage_dir)?.unwrap_or_default());
+        let balances = match Balances::load(&storage_dir)? {
+            Some(balances) => balances,
+            None => Balances::from_manifest(&ctx.project_root_dir)?,
+        };
+        let balances = self
+            .execution
+            .balances
+            .apply(balances, &ctx.address_declarations())?;
+
+        execute(
+            ctx,
+            &call,
+            signers,
+            args,
+            type_tag,
+            &ExecutionParams {
+                storage_dir: storage_dir.clone(),
+                gas_budget: self.gas_budget,
+                dry_run: self.dry_run,
+                json: self.json,
+                gas_profile: self.gas_profile,
+                fork: self.fork_url.take().map(|url| ForkParams {
+                    url,
+                    height: self.height.take(),
+                }),
+                balances,
+                chain_env,
+            },
+        )?;
+
+        if !self.dry_run {
+            chain_env.save(&storage_dir)?;
+        }
+        Ok(())
+    }
 
-    map.get(&hash)
-        .cloned()
-        .ok_or_else(|| anyhow!("Script location not found"))
+    /// Loads the stored transaction and replaces signer placeholders with the passed signers.
+    fn load_transaction(&mut self, ctx: &Context) -> Result<LocalCall> {
+        ensure!(
+            self.call.type_parameters.is_none()
+                && self.call.params.is_none()
+                && self.call.args_file.is_none(),
+            "The transaction file already contains type parameters and arguments"
+        );
+        let path = ctx.project_root_dir.join(&self.call.call);
+        let bytes = fs::read(&path)
+            .map_err(|err| anyhow!("Failed to read transaction {:?}: {}", path, err))?;
+        let tx = Transaction::decode(&bytes)
+            .map_err(|err| anyhow!("{:?}: {}", path, err))?
+            .inner();
+
+        let addr_map = ctx.address_declarations();
+        let placeholders = tx
+            .signers
+            .iter()
+            .filter(|signer| **signer == Signer::Placeholder)
+            .count();
+        ensure!(
+            placeholders == self.signers.len(),
+            "The transaction contains {} signer placeholders, {} signers are passed",
+            placeholders,
+            self.signers.len()
+        );
+        let mut explicit = self.signers.iter();
+        let signers = tx
+            .signers
+            .iter()
+            .map(|signer| match signer {
+                Signer::Root => Ok(diem_root_address()),
+                Signer::Placeholder => match explicit.next() {
+                    Some(signer) => parse_signer(signer, &addr_map),
+                    None => bail!("Missing signer"),
+                },
+                Signer::Name(name) => addr_map
+                    .get(name)
+                    .and_then(|addr| *addr)
+                    .ok_or_else(|| anyhow!("Failed to find address with name:{}", name)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((tx.call, signers, tx.args, tx.type_args))
+    }
 }
 
-/// Search "move" files in the project
-/// Search is carried out in the directories: scripts, sources
-///
-fn find_move_files_in_project(project_path: &Path) -> HashMap<FileHash, PathBuf> {
-    ["scripts", "sources"]
-        .iter()
-        .filter_map(|dir| find_move_files_in_dir(&project_path.join(dir)).ok())
-        .flatten()
-        .collect()
-}
+/// Call, signers, arguments and type arguments of the local execution.
+type LocalCall = (Call, Vec<AccountAddress>, Vec<Vec<u8>>, Vec<TypeTag>);
 
-/// Search "move" files in directory
-/// Recursive search
-fn find_move_files_in_dir(dir_path: &Path) -> Result<Vec<(FileHash, PathBuf)>> {
-    let list = fs::read_dir(dir_path)?
-        .filter_map(|dir| dir.ok())
-        .map(|r| r.path())
-        .filter_map(|path| {
-            if path.is_dir() {
-                find_move_files_in_dir(&path).ok()
-            } else if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("move")
-            {
-                let file = fs::read_to_string(&path).ok()?;
-                let hash = FileHash::new(&file);
-                Some(vec![(hash, path)])
-            } else {
-                None
-            }
-        })
-        .flatten()
-        .collect();
-    Ok(list)
+/// Returns true if the call is a path to the transaction file.
+fn is_tx_file(call: &str) -> bool {
+    Path::new(call)
+        .extension()
+        .map(|ext| ext == "mvt")
+        .unwrap_or_default()
 }
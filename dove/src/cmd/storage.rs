@@ -0,0 +1,241 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use anyhow::{Error, Result};
+use clap::Parser;
+use move_cli::DEFAULT_STORAGE_DIR;
+
+use crate::balances::{Balances, BALANCES_FILE};
+use crate::chain_env::CHAIN_ENV_FILE;
+use crate::context::Context;
+use crate::executor::DELETED_RESOURCES_FILE;
+
+/// Directory with the storage snapshots: PROJECT_DIR/snapshots
+/// It is kept outside of the build directory to survive `dove clean`.
+const SNAPSHOTS_DIR: &str = "snapshots";
+
+/// Sandbox storage snapshots
+#[derive(Debug, Parser)]
+pub enum StorageCommand {
+    /// Save the current sandbox storage under a name
+    #[clap(name = "snapshot")]
+    Snapshot {
+        /// Snapshot name
+        name: String,
+
+        /// Sandbox storage directory
+        #[clap(long, default_value = DEFAULT_STORAGE_DIR)]
+        storage: PathBuf,
+
+        /// Overwrite the snapshot if it already exists
+        #[clap(long)]
+        force: bool,
+    },
+
+    /// Replace the sandbox storage with the named snapshot
+    #[clap(name = "restore")]
+    Restore {
+        /// Snapshot name
+        name: String,
+
+        /// Sandbox storage directory
+        #[clap(long, default_value = DEFAULT_STORAGE_DIR)]
+        storage: PathBuf,
+    },
+
+    /// Print the simulated PONT balances stored in the sandbox storage
+    #[clap(name = "balances")]
+    Balances {
+        /// Sandbox storage directory
+        #[clap(long, default_value = DEFAULT_STORAGE_DIR)]
+        storage: PathBuf,
+    },
+}
+
+impl StorageCommand {
+    pub fn apply(&mut self, ctx: &mut Context) -> Result<()> {
+        match self {
+            StorageCommand::Snapshot {
+                name,
+                storage,
+                force,
+            } => {
+                let storage = storage_dir(&ctx.project_root_dir, storage)?;
+                ensure!(
+                    storage.exists(),
+                    "Storage directory {} does not exist",
+                    storage.display()
+                );
+                let snapshot = snapshot_dir(&ctx.project_root_dir, name)?;
+                if snapshot.exists() {
+                    ensure!(
+                        *force,
+                        r#"Snapshot "{}" already exists. Use --force to overwrite it"#,
+                        name
+                    );
+                    fs::remove_dir_all(&snapshot)?;
+                }
+                copy_dir(&storage, &snapshot)?;
+                println!(r#"Snapshot "{}" saved"#, name);
+                Ok(())
+            }
+            StorageCommand::Restore { name, storage } => {
+                let snapshot = snapshot_dir(&ctx.project_root_dir, name)?;
+                ensure!(snapshot.exists(), r#"Snapshot "{}" not found"#, name);
+                let storage = storage_dir(&ctx.project_root_dir, storage)?;
+                if storage.exists() {
+                    ensure!(
+                        is_sandbox_storage(&storage)?,
+                        "{} does not look like a sandbox storage directory. Refusing to replace it",
+                        storage.display()
+                    );
+                    fs::remove_dir_all(&storage)?;
+                }
+                copy_dir(&snapshot, &storage)?;
+                println!(r#"Snapshot "{}" restored"#, name);
+                Ok(())
+            }
+            StorageCommand::Balances { storage } => {
+                match Balances::load(&ctx.project_root_dir.join(storage))? {
+                    Some(balances) if !balances.is_empty() => print!("{}", balances.to_text()),
+                    _ => println!("PONT balances: none"),
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Returns the directory of the named snapshot.
+pub fn snapshot_dir(project_root_dir: &Path, name: &str) -> Result<PathBuf, Error> {
+    ensure!(
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-' || ch == '.')
+            && name != "."
+            && name != "..",
+        r#"Invalid snapshot name "{}". Use letters, digits, '_', '-' and '.'"#,
+        name
+    );
+    Ok(project_root_dir.join(SNAPSHOTS_DIR).join(name))
+}
+
+/// Returns the sandbox storage directory.
+/// The directory must not contain the project or the snapshots and must not be inside the snapshots.
+fn storage_dir(project_root_dir: &Path, storage: &Path) -> Result<PathBuf, Error> {
+    let root = normalize(project_root_dir);
+    let storage = normalize(&root.join(storage));
+    let snapshots = root.join(SNAPSHOTS_DIR);
+    ensure!(
+        !root.starts_with(&storage),
+        "Storage directory {} contains the project",
+        storage.display()
+    );
+    ensure!(
+        !snapshots.starts_with(&storage) && !storage.starts_with(&snapshots),
+        "Storage directory {} overlaps the snapshots directory",
+        storage.display()
+    );
+    Ok(storage)
+}
+
+/// Resolves `.` and `..` without touching the file system: the storage may not exist yet.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Returns true if the directory is empty or holds only the sandbox storage layout:
+/// `0x...` account directories and the dove state files.
+fn is_sandbox_storage(dir: &Path) -> Result<bool, Error> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let is_storage_entry = if entry.file_type()?.is_dir() {
+            name.len() > 2
+                && name.starts_with("0x")
+                && name[2..].chars().all(|ch| ch.is_ascii_hexdigit())
+        } else {
+            name == CHAIN_ENV_FILE || name == BALANCES_FILE || name == DELETED_RESOURCES_FILE
+        };
+        if !is_storage_entry {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Recursively copies the directory.
+fn copy_dir(from: &Path, to: &Path) -> Result<(), Error> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+        if path.is_dir() {
+            copy_dir(&path, &target)?;
+        } else {
+            fs::copy(&path, &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use super::{is_sandbox_storage, snapshot_dir, storage_dir};
+
+    #[test]
+    fn test_snapshot_dir() {
+        let root = Path::new("project");
+        assert_eq!(
+            snapshot_dir(root, "step_1").unwrap(),
+            root.join("snapshots").join("step_1")
+        );
+        snapshot_dir(root, "").unwrap_err();
+        snapshot_dir(root, "..").unwrap_err();
+        snapshot_dir(root, "a/b").unwrap_err();
+    }
+
+    #[test]
+    fn test_storage_dir() {
+        let root = Path::new("/project");
+        assert_eq!(
+            storage_dir(root, Path::new("build/storage")).unwrap(),
+            root.join("build").join("storage")
+        );
+        assert_eq!(
+            storage_dir(root, Path::new("./build/../storage")).unwrap(),
+            root.join("storage")
+        );
+        storage_dir(root, Path::new(".")).unwrap_err();
+        storage_dir(root, Path::new("build/../..")).unwrap_err();
+        storage_dir(root, Path::new("snapshots")).unwrap_err();
+        storage_dir(root, Path::new("snapshots/step_1")).unwrap_err();
+    }
+
+    #[test]
+    fn test_is_sandbox_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(is_sandbox_storage(dir.path()).unwrap());
+
+        fs::create_dir_all(dir.path().join("0x1").join("resources")).unwrap();
+        fs::write(dir.path().join("balances.json"), "{}").unwrap();
+        assert!(is_sandbox_storage(dir.path()).unwrap());
+
+        fs::write(dir.path().join("Move.toml"), "").unwrap();
+        assert!(!is_sandbox_storage(dir.path()).unwrap());
+    }
+}
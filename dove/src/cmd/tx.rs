@@ -0,0 +1,330 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Error, Result};
+use clap::Parser;
+use itertools::Itertools;
+use serde_json::{json, Value};
+use move_binary_format::binary_views::BinaryIndexedView;
+use move_binary_format::file_format::{CompiledScript, empty_module};
+use move_command_line_common::files::FileHash;
+use move_disassembler::disassembler::Disassembler;
+use move_ir_types::location::Loc;
+use lang::bytecode::accessor::{Bytecode, BytecodeRef, BytecodeType};
+use lang::bytecode::info::{BytecodeInfo, Script, Type};
+use crate::context::Context;
+use crate::call::args_file::parameter_names;
+use crate::call::fn_call::find_modules;
+use crate::call::model::{Call, ScriptArg, Transaction, TxMetadata};
+
+/// Transaction tools
+#[derive(Debug, Parser)]
+pub enum TxCommand {
+    /// Decode the transaction file and print its content
+    #[clap(name = "inspect")]
+    Inspect {
+        /// Path to the transaction file (.mvt)
+        path: PathBuf,
+
+        /// Print the transaction in JSON format
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+impl TxCommand {
+    pub fn apply(&mut self, ctx: &mut Context) -> Result<()> {
+        match self {
+            TxCommand::Inspect { path, json } => {
+                let tx = inspect(ctx, path)?;
+                if *json {
+                    println!("{}", serde_json::to_string_pretty(&tx.to_json())?);
+                } else {
+                    print!("{}", tx.to_text());
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Decoded transaction.
+struct InspectedTx {
+    version: u8,
+    meta: TxMetadata,
+    signers: Vec<String>,
+    target: Target,
+    type_args: Vec<String>,
+    args: Vec<InspectedArg>,
+}
+
+/// Call target.
+enum Target {
+    Script { code: Vec<u8>, disassembly: String },
+    Function { name: String },
+}
+
+/// Transaction argument.
+struct InspectedArg {
+    /// Parameter name if known.
+    name: Option<String>,
+    /// Parameter type and decoded value, if the target signature is known.
+    value: Option<(Type, ScriptArg)>,
+    /// BCS-encoded argument.
+    bcs: Vec<u8>,
+}
+
+fn inspect(ctx: &Context, path: &Path) -> Result<InspectedTx, Error> {
+    let bytes = fs::read(path)
+        .map_err(|err| anyhow!("Failed to read transaction {:?}: {}", path, err))?;
+    let tx = Transaction::decode(&bytes).map_err(|err| anyhow!("{:?}: {}", path, err))?;
+    let version = tx.version();
+    let tx = tx.inner();
+
+    let (target, signature) = match tx.call {
+        Call::Script { code } => {
+            let script = CompiledScript::deserialize(&code)
+                .map_err(|err| anyhow!("Failed to deserialize script bytecode: {:?}", err))?;
+            let disassembly = Disassembler::from_view(
+                BinaryIndexedView::Script(&script),
+                Loc::new(FileHash::empty(), 0, 0),
+            )?
+            .disassemble()?;
+
+            let name = path
+                .file_stem()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| "main".to_string());
+            let info = BytecodeInfo::from(Bytecode::Script(
+                name.clone(),
+                script,
+                Box::new(empty_module()),
+                BytecodeRef(path.to_string_lossy().to_string(), BytecodeType::Script),
+            ));
+            let signature = info.find_script_function(&name).map(|f| (info, f));
+            (Target::Script { code, disassembly }, signature)
+        }
+        Call::ScriptFunction {
+            mod_address,
+            mod_name,
+            func_name,
+        } => {
+            let signature = find_modules(ctx, Some(mod_address), &mod_name, None)?
+                .into_iter()
+                .find_map(|info| {
+                    info.find_script_function(func_name.as_str())
+                        .map(|f| (info, f))
+                });
+            let name = format!(
+                "0x{}::{}::{}",
+                mod_address.short_str_lossless(),
+                mod_name,
+                func_name
+            );
+            (Target::Function { name }, signature)
+        }
+    };
+
+    let args = match signature {
+        Some((info, script)) => decode_args(&info, script, tx.args)?,
+        None => tx
+            .args
+            .into_iter()
+            .map(|bcs| InspectedArg {
+                name: None,
+                value: None,
+                bcs,
+            })
+            .collect(),
+    };
+
+    Ok(InspectedTx {
+        version,
+        meta: tx.meta,
+        signers: tx.signers.iter().map(|signer| signer.to_string()).collect(),
+        target,
+        type_args: tx.type_args.iter().map(|tp| tp.to_string()).collect(),
+        args,
+    })
+}
+
+/// Decodes arguments against the target parameter types.
+fn decode_args(
+    info: &BytecodeInfo,
+    script: Script,
+    args: Vec<Vec<u8>>,
+) -> Result<Vec<InspectedArg>, Error> {
+    let signers_count = script
+        .parameters
+        .iter()
+        .take_while(|tp| **tp == Type::Signer)
+        .count();
+    ensure!(
+        args.len() == script.parameters.len() - signers_count,
+        "The function accepts {} parameters, the transaction contains {} arguments",
+        script.parameters.len() - signers_count,
+        args.len()
+    );
+    let names = parameter_names(info, &script)
+        .filter(|names| names.len() == script.parameters.len())
+        .map(|names| names.into_iter().skip(signers_count).map(Some).collect())
+        .unwrap_or_else(|| vec![None; args.len()]);
+
+    script
+        .parameters
+        .into_iter()
+        .skip(signers_count)
+        .zip(names)
+        .zip(args)
+        .enumerate()
+        .map(|(index, ((tp, name), bcs))| {
+            let arg = ScriptArg::decode(&tp, &bcs)
+                .map_err(|err| anyhow!("Failed to decode argument {}: {}", index, err))?;
+            Ok(InspectedArg {
+                name,
+                value: Some((tp, arg)),
+                bcs,
+            })
+        })
+        .collect()
+}
+
+impl InspectedTx {
+    fn to_text(&self) -> String {
+        let mut out = format!("Version: {}\n", self.version);
+        if let Some(chain_id) = &self.meta.chain_id {
+            out.push_str(&format!("Chain id: {}\n", chain_id));
+        }
+        if let Some(expiration_block) = &self.meta.expiration_block {
+            out.push_str(&format!("Expiration block: {}\n", expiration_block));
+        }
+        if let Some(memo) = &self.meta.memo {
+            out.push_str(&format!("Memo: {:?}\n", memo));
+        }
+        out.push_str(&format!("Signers: [{}]\n", self.signers.join(", ")));
+        match &self.target {
+            Target::Script { .. } => out.push_str("Call: script\n"),
+            Target::Function { name } => out.push_str(&format!("Call: {}\n", name)),
+        }
+        out.push_str(&format!(
+            "Type arguments: [{}]\n",
+            self.type_args.join(", ")
+        ));
+        out.push_str("Arguments:\n");
+        for (index, arg) in self.args.iter().enumerate() {
+            let name = arg.name.clone().unwrap_or_else(|| format!("#{}", index));
+            match &arg.value {
+                Some((tp, value)) => out.push_str(&format!(
+                    "    {}: {} = {}\n",
+                    name,
+                    tp,
+                    text_value(tp, value)
+                )),
+                None => out.push_str(&format!("    {}: bcs = {}\n", name, hex::encode(&arg.bcs))),
+            }
+        }
+        if let Target::Script { disassembly, .. } = &self.target {
+            out.push_str("Script:\n");
+            for line in disassembly.lines() {
+                out.push_str(&format!("    {}\n", line));
+            }
+        }
+        out
+    }
+
+    fn to_json(&self) -> Value {
+        let call = match &self.target {
+            Target::Script { code, disassembly } => json!({
+                "script": {
+                    "code": hex::encode(code),
+                    "disassembly": disassembly,
+                }
+            }),
+            Target::Function { name } => json!({ "function": name }),
+        };
+        let args = self
+            .args
+            .iter()
+            .map(|arg| {
+                let mut value = json!({
+                    "name": arg.name,
+                    "bcs": hex::encode(&arg.bcs),
+                });
+                if let Some((tp, val)) = &arg.value {
+                    value["type"] = Value::String(tp.to_string());
+                    value["value"] = json_value(tp, val);
+                }
+                value
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "version": self.version,
+            "chain_id": self.meta.chain_id,
+            "expiration_block": self.meta.expiration_block,
+            "memo": self.meta.memo,
+            "signers": self.signers,
+            "call": call,
+            "type_args": self.type_args,
+            "args": args,
+        })
+    }
+}
+
+/// Formats the argument in the call syntax.
+fn text_value(tp: &Type, arg: &ScriptArg) -> String {
+    match (tp, arg) {
+        (Type::String | Type::AsciiString, ScriptArg::Vector(bytes)) => {
+            format!("{:?}", String::from_utf8_lossy(&to_bytes(bytes)))
+        }
+        (Type::Vector(el), ScriptArg::Vector(bytes)) if **el == Type::U8 => {
+            format!("x\"{}\"", hex::encode(to_bytes(bytes)))
+        }
+        (Type::Vector(el), ScriptArg::Vector(values)) => format!(
+            "[{}]",
+            values.iter().map(|val| text_value(el, val)).join(", ")
+        ),
+        (_, arg) => scalar_value(arg),
+    }
+}
+
+/// Formats the argument as a JSON value.
+/// Numbers that do not fit into u64 are represented as strings.
+fn json_value(tp: &Type, arg: &ScriptArg) -> Value {
+    match (tp, arg) {
+        (Type::String | Type::AsciiString, ScriptArg::Vector(bytes)) => {
+            Value::String(String::from_utf8_lossy(&to_bytes(bytes)).to_string())
+        }
+        (Type::Vector(el), ScriptArg::Vector(bytes)) if **el == Type::U8 => {
+            Value::String(hex::encode(to_bytes(bytes)))
+        }
+        (Type::Vector(el), ScriptArg::Vector(values)) => {
+            Value::Array(values.iter().map(|val| json_value(el, val)).collect())
+        }
+        (_, ScriptArg::U8(val)) => json!(val),
+        (_, ScriptArg::U64(val)) => json!(val),
+        (_, ScriptArg::Bool(val)) => json!(val),
+        (_, arg) => Value::String(scalar_value(arg)),
+    }
+}
+
+fn scalar_value(arg: &ScriptArg) -> String {
+    match arg {
+        ScriptArg::U8(val) => val.to_string(),
+        ScriptArg::U64(val) => val.to_string(),
+        ScriptArg::U128(val) => val.to_string(),
+        ScriptArg::U256(val) => val.to_string(),
+        ScriptArg::Bool(val) => val.to_string(),
+        ScriptArg::Address(val) => format!("0x{}", val.short_str_lossless()),
+        ScriptArg::Vector(values) => format!("[{}]", values.iter().map(scalar_value).join(", ")),
+    }
+}
+
+fn to_bytes(values: &[ScriptArg]) -> Vec<u8> {
+    values
+        .iter()
+        .filter_map(|val| match val {
+            ScriptArg::U8(val) => Some(*val),
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,237 @@
+use anyhow::{Error, Result};
+use serde_json::{json, Value};
+
+use move_core_types::account_address::AccountAddress;
+use move_core_types::effects::{ChangeSet, Event};
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
+use move_core_types::resolver::MoveResolver;
+use move_resource_viewer::{AnnotatedMoveValue, MoveValueAnnotator};
+use resource_viewer::ser::AnnotatedMoveValueWrapper;
+
+/// Kind of the storage change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+impl ChangeKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Created => "created",
+            ChangeKind::Modified => "modified",
+            ChangeKind::Deleted => "deleted",
+        }
+    }
+}
+
+/// Emitted event.
+pub struct EventInfo {
+    /// Event handle key.
+    pub key: Vec<u8>,
+    /// Sequence number of the event.
+    pub sequence_number: u64,
+    /// Event type.
+    pub tp: TypeTag,
+    /// Decoded event value. None if the event can't be decoded.
+    pub value: Option<AnnotatedMoveValue>,
+    /// BCS-encoded event value.
+    pub data: Vec<u8>,
+}
+
+/// Changed resource.
+pub struct ResourceChange {
+    pub address: AccountAddress,
+    pub tag: StructTag,
+    pub kind: ChangeKind,
+}
+
+/// Changed module.
+pub struct ModuleChange {
+    pub id: ModuleId,
+    pub kind: ChangeKind,
+}
+
+/// Events and write-set of the execution.
+pub struct ExecutionEffects {
+    pub events: Vec<EventInfo>,
+    pub resources: Vec<ResourceChange>,
+    pub modules: Vec<ModuleChange>,
+}
+
+impl ExecutionEffects {
+    /// Decodes the events and classifies the changes against the state before they are committed.
+    pub fn new<R: MoveResolver>(
+        state: &R,
+        changeset: &ChangeSet,
+        events: &[Event],
+    ) -> Result<ExecutionEffects, Error> {
+        let annotator = MoveValueAnnotator::new(state);
+        let events = events
+            .iter()
+            .map(|(key, sequence_number, tp, data)| EventInfo {
+                key: key.clone(),
+                sequence_number: *sequence_number,
+                tp: tp.clone(),
+                value: annotator.view_value(tp, data).ok(),
+                data: data.clone(),
+            })
+            .collect();
+
+        let mut resources = vec![];
+        let mut modules = vec![];
+        for (address, account) in changeset.accounts() {
+            for (tag, blob) in account.resources() {
+                let exists = state
+                    .get_resource(address, tag)
+                    .map_err(|err| anyhow!("Failed to load resource {}: {:?}", tag, err))?
+                    .is_some();
+                resources.push(ResourceChange {
+                    address: *address,
+                    tag: tag.clone(),
+                    kind: change_kind(exists, blob.is_some()),
+                });
+            }
+            for (name, blob) in account.modules() {
+                let id = ModuleId::new(*address, name.clone());
+                let exists = state
+                    .get_module(&id)
+                    .map_err(|err| anyhow!("Failed to load module {}: {:?}", id, err))?
+                    .is_some();
+                modules.push(ModuleChange {
+                    id,
+                    kind: change_kind(exists, blob.is_some()),
+                });
+            }
+        }
+
+        Ok(ExecutionEffects {
+            events,
+            resources,
+            modules,
+        })
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        if self.events.is_empty() {
+            out.push_str("Events: none\n");
+        } else {
+            out.push_str("Events:\n");
+            for event in &self.events {
+                out.push_str(&format!(
+                    "    {} #{} (key: {})\n",
+                    event.tp,
+                    event.sequence_number,
+                    hex::encode(&event.key)
+                ));
+                match &event.value {
+                    Some(value) => {
+                        for line in value.to_string().lines() {
+                            out.push_str(&format!("        {}\n", line));
+                        }
+                    }
+                    None => out.push_str(&format!("        bcs: {}\n", hex::encode(&event.data))),
+                }
+            }
+        }
+
+        if self.resources.is_empty() {
+            out.push_str("Resources: no changes\n");
+        } else {
+            out.push_str("Resources:\n");
+            for resource in &self.resources {
+                out.push_str(&format!(
+                    "    {} {} at 0x{}\n",
+                    resource.kind.as_str(),
+                    resource.tag,
+                    resource.address.short_str_lossless()
+                ));
+            }
+        }
+
+        if self.modules.is_empty() {
+            out.push_str("Modules: no changes\n");
+        } else {
+            out.push_str("Modules:\n");
+            for module in &self.modules {
+                out.push_str(&format!(
+                    "    {} 0x{}::{}\n",
+                    module.kind.as_str(),
+                    module.id.address().short_str_lossless(),
+                    module.id.name()
+                ));
+            }
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> Result<Value, Error> {
+        let events = self
+            .events
+            .iter()
+            .map(|event| {
+                Ok(json!({
+                    "key": hex::encode(&event.key),
+                    "sequence_number": event.sequence_number,
+                    "type": event.tp.to_string(),
+                    "value": event
+                        .value
+                        .as_ref()
+                        .map(|value| serde_json::to_value(AnnotatedMoveValueWrapper(value)))
+                        .transpose()?,
+                    "data": hex::encode(&event.data),
+                }))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let resources = self
+            .resources
+            .iter()
+            .map(|resource| {
+                json!({
+                    "address": format!("0x{}", resource.address.short_str_lossless()),
+                    "type": resource.tag.to_string(),
+                    "change": resource.kind.as_str(),
+                })
+            })
+            .collect::<Vec<_>>();
+        let modules = self
+            .modules
+            .iter()
+            .map(|module| {
+                json!({
+                    "address": format!("0x{}", module.id.address().short_str_lossless()),
+                    "name": module.id.name().as_str(),
+                    "change": module.kind.as_str(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(json!({
+            "events": events,
+            "resources": resources,
+            "modules": modules,
+        }))
+    }
+}
+
+fn change_kind(exists: bool, written: bool) -> ChangeKind {
+    match (exists, written) {
+        (_, false) => ChangeKind::Deleted,
+        (true, true) => ChangeKind::Modified,
+        (false, true) => ChangeKind::Created,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{change_kind, ChangeKind};
+
+    #[test]
+    fn test_change_kind() {
+        assert_eq!(change_kind(false, true), ChangeKind::Created);
+        assert_eq!(change_kind(true, true), ChangeKind::Modified);
+        assert_eq!(change_kind(true, false), ChangeKind::Deleted);
+    }
+}
@@ -0,0 +1,316 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use anyhow::{Error, Result};
+use regex::Regex;
+
+use move_core_types::account_address::AccountAddress;
+use move_core_types::errmap::{ErrorDescription, ErrorMapping};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
+use move_model::ast::Value;
+use move_model::model::GlobalEnv;
+use move_package::{BuildConfig, ModelConfig};
+use move_package::compilation::package_layout::CompiledPackageLayout;
+
+use crate::context::Context;
+
+/// Prefix of the error constants.
+const ERROR_PREFIX: &str = "E";
+
+/// Name of the project error mapping file: PROJECT_DIR/build/<package>/error_map.errmap
+const ERROR_MAP_FILE: &str = "error_map.errmap";
+
+/// Merges the error mapping of the project modules and their dependencies
+/// into the context error mapping.
+pub fn update_project_error_mapping(ctx: &mut Context) -> Result<(), Error> {
+    let mapping =
+        cached_project_error_mapping(&ctx.project_root_dir, &ctx.move_args.build_config)?;
+    merge(&mut ctx.error_descriptions, mapping);
+    Ok(())
+}
+
+/// Returns the error mapping of the project modules and their dependencies.
+/// The mapping is stored in the build directory and reused while the bytecode is not rebuilt.
+pub fn cached_project_error_mapping(
+    project_dir: &Path,
+    build_config: &BuildConfig,
+) -> Result<ErrorMapping, Error> {
+    let package = get_package_name(project_dir)
+        .ok_or_else(|| anyhow!("Failed to read the package name from Move.toml"))?;
+    let path = error_map_path(project_dir, &package);
+    let bytecode_dir = project_dir
+        .join(CompiledPackageLayout::Root.path())
+        .join(&package)
+        .join(CompiledPackageLayout::CompiledModules.path());
+    if is_actual(&path, &bytecode_dir) {
+        return load(&path);
+    }
+
+    let mapping = project_error_mapping(project_dir, build_config)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, bcs::to_bytes(&mapping)?)?;
+    Ok(mapping)
+}
+
+/// Builds the error mapping from the error constants of the project modules and their dependencies.
+/// Error constants are the `u64` constants with the `E` prefix, the doc comment of the constant
+/// is used as the error description.
+pub fn project_error_mapping(
+    project_dir: &Path,
+    build_config: &BuildConfig,
+) -> Result<ErrorMapping, Error> {
+    let env = build_config.clone().move_model_for_package(
+        project_dir,
+        ModelConfig {
+            all_files_as_targets: true,
+            target_filter: None,
+        },
+    )?;
+    ensure!(
+        !env.has_errors(),
+        "Failed to build the error mapping: the package has compilation errors"
+    );
+    Ok(error_mapping(&env))
+}
+
+fn error_mapping(env: &GlobalEnv) -> ErrorMapping {
+    let mut mapping = ErrorMapping::default();
+    for module in env.get_modules() {
+        if module.is_script_module() {
+            continue;
+        }
+        let name = module.get_name();
+        let address = AccountAddress::from_hex_literal(&format!("0x{:x}", name.addr()));
+        let module_name = Identifier::new(env.symbol_pool().string(name.name()).as_str());
+        let module_id = match (address, module_name) {
+            (Ok(address), Ok(module_name)) => ModuleId::new(address, module_name),
+            _ => continue,
+        };
+
+        for constant in module.get_named_constants() {
+            let code_name = env.symbol_pool().string(constant.get_name()).to_string();
+            if !code_name.starts_with(ERROR_PREFIX) {
+                continue;
+            }
+            let code = match constant.get_value() {
+                Value::Number(code) => match code.to_string().parse::<u64>() {
+                    Ok(code) => code,
+                    Err(_) => continue,
+                },
+                _ => continue,
+            };
+            mapping
+                .module_error_maps
+                .entry(module_id.clone())
+                .or_default()
+                .entry(code)
+                .or_insert(ErrorDescription {
+                    code_name,
+                    code_description: constant.get_doc().trim().to_string(),
+                });
+        }
+    }
+    mapping
+}
+
+/// Merges the other mapping into the base mapping.
+/// The other (project) descriptions win: they are built from the current sources,
+/// while the base mapping is bundled with dove and may be stale.
+pub fn merge(base: &mut ErrorMapping, other: ErrorMapping) {
+    for (category, description) in other.error_categories {
+        base.error_categories.insert(category, description);
+    }
+    for (module_id, errors) in other.module_error_maps {
+        base.module_error_maps
+            .entry(module_id)
+            .or_default()
+            .extend(errors);
+    }
+}
+
+/// Appends the abort explanation to the node error.
+pub fn explain_node_error(mapping: &ErrorMapping, err: Error) -> Error {
+    let message = err.to_string();
+    match explain_abort(mapping, &message).or_else(|| explain_pallet_error(&message)) {
+        Some(explanation) => err.context(explanation),
+        None => err,
+    }
+}
+
+/// Explains the abort code of the module.
+/// The code is looked up as the raw error constant (`abort ECONST`) first,
+/// then as the category and the reason (`abort Errors::invalid_argument(ECONST)`).
+pub fn explain_abort_code(
+    mapping: &ErrorMapping,
+    module_id: &ModuleId,
+    code: u64,
+) -> Option<String> {
+    if let Some(description) = mapping
+        .module_error_maps
+        .get(module_id)
+        .and_then(|errors| errors.get(&code))
+    {
+        return Some(format!(
+            "Execution aborted in {} with code {}: {} ({})",
+            module_id, code, description.code_name, description.code_description
+        ));
+    }
+
+    let context = mapping.get_explanation(module_id, code)?;
+    Some(format!(
+        "Execution aborted in {} with code {}: {} ({}). Category: {} ({})",
+        module_id,
+        code,
+        context.reason.code_name,
+        context.reason.code_description,
+        context.category.code_name,
+        context.category.code_description
+    ))
+}
+
+/// Explains the abort with the location and the code in the message: `... 0x1::Module ... abort code 2`.
+fn explain_abort(mapping: &ErrorMapping, message: &str) -> Option<String> {
+    let location = Regex::new(r"(0x[0-9a-fA-F]+)::([A-Za-z_][A-Za-z0-9_]*)").ok()?;
+    let code = Regex::new(r"(?i)abort[^0-9]*?\bcode\b\D*?(\d+)").ok()?;
+
+    let code = code
+        .captures(message)?
+        .get(1)?
+        .as_str()
+        .parse::<u64>()
+        .ok()?;
+    let location = location.captures(message)?;
+    let module_id = ModuleId::new(
+        AccountAddress::from_hex_literal(location.get(1)?.as_str()).ok()?,
+        Identifier::new(location.get(2)?.as_str()).ok()?,
+    );
+    explain_abort_code(mapping, &module_id, code)
+}
+
+/// Explains the `Mvm` pallet error of the node: `Runtime error: Runtime module error: Aborted from Mvm`.
+/// The pallet reports the VM status only, the abort code and the location are not included.
+fn explain_pallet_error(message: &str) -> Option<String> {
+    let error = Regex::new(r"(\w+) from Mvm\b").ok()?;
+    match error.captures(message)?.get(1)?.as_str() {
+        "Aborted" => Some(
+            "The transaction was aborted by the Move code. The node does not report the abort code: \
+            run the transaction file with `dove run` to get the explained abort"
+                .to_string(),
+        ),
+        _ => None,
+    }
+}
+
+fn error_map_path(project_dir: &Path, package: &str) -> PathBuf {
+    project_dir.join("build").join(package).join(ERROR_MAP_FILE)
+}
+
+fn load(path: &Path) -> Result<ErrorMapping, Error> {
+    Ok(bcs::from_bytes(&fs::read(path)?)?)
+}
+
+/// Returns true if the error mapping is newer than the compiled bytecode.
+fn is_actual(path: &Path, bytecode_dir: &Path) -> bool {
+    let modified = match fs::metadata(path).and_then(|meta| meta.modified()) {
+        Ok(modified) => modified,
+        Err(_) => return false,
+    };
+    last_modified(bytecode_dir)
+        .map(|bytecode| bytecode <= modified)
+        .unwrap_or(false)
+}
+
+fn last_modified(dir: &Path) -> Option<SystemTime> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                last_modified(&path)
+            } else {
+                entry.metadata().and_then(|meta| meta.modified()).ok()
+            }
+        })
+        .max()
+}
+
+fn get_package_name(project_dir: &Path) -> Option<String> {
+    let manifest = fs::read_to_string(project_dir.join("Move.toml")).ok()?;
+    let manifest = toml::from_str::<toml::Value>(&manifest).ok()?;
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(|name| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::errmap::{ErrorDescription, ErrorMapping};
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::{ModuleId, CORE_CODE_ADDRESS};
+    use super::{explain_abort, explain_node_error, merge};
+
+    fn description(name: &str) -> ErrorDescription {
+        ErrorDescription {
+            code_name: name.to_string(),
+            code_description: format!("{} description", name),
+        }
+    }
+
+    #[test]
+    fn test_merge_and_explain() {
+        let module_id = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("Store").unwrap());
+        let mut base = ErrorMapping::default();
+        base.error_categories
+            .insert(7, description("INVALID_ARGUMENT"));
+        base.module_error_maps
+            .entry(module_id.clone())
+            .or_default()
+            .insert(1, description("EBASE"));
+
+        let mut project = ErrorMapping::default();
+        let errors = project.module_error_maps.entry(module_id).or_default();
+        errors.insert(1, description("EPROJECT"));
+        errors.insert(2, description("ENOT_FOUND"));
+        merge(&mut base, project);
+
+        // category and reason
+        let explanation =
+            explain_abort(&base, "Move abort in 0x1::Store: aborted with code 519").unwrap();
+        assert!(explanation.contains("ENOT_FOUND"));
+        assert!(explanation.contains("INVALID_ARGUMENT"));
+        // the project description replaces the base one
+        let explanation =
+            explain_abort(&base, "Move abort in 0x1::Store: abort code 263").unwrap();
+        assert!(explanation.contains("EPROJECT"));
+        // raw error constant
+        let explanation = explain_abort(&base, "Move abort in 0x1::Store: abort code 2").unwrap();
+        assert!(explanation.contains("ENOT_FOUND"));
+        assert!(!explanation.contains("Category"));
+        assert!(explain_abort(&base, "Unknown error").is_none());
+    }
+
+    #[test]
+    fn test_explain_node_error() {
+        // subxt error of the failed extrinsic
+        let err = explain_node_error(
+            &ErrorMapping::default(),
+            anyhow!("Runtime error: Runtime module error: Aborted from Mvm"),
+        );
+        assert!(format!("{:?}", err).contains("dove run"));
+
+        let err = explain_node_error(
+            &ErrorMapping::default(),
+            anyhow!("Runtime error: Runtime module error: InsufficientBalance from Balances"),
+        );
+        assert_eq!(
+            err.to_string(),
+            "Runtime error: Runtime module error: InsufficientBalance from Balances"
+        );
+    }
+}
@@ -0,0 +1,524 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::errors::VMError;
+use move_binary_format::file_format::{CompiledModule, FunctionDefinitionIndex};
+use move_cli::Move;
+use move_cli::sandbox::cli::PackageContext;
+use move_cli::sandbox::utils::{
+    explain_execution_effects, explain_execution_error, get_gas_status, maybe_commit_effects,
+    on_disk_state_view::OnDiskStateView,
+};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::effects::ChangeSet;
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
+use move_core_types::resolver::{ModuleResolver, ResourceResolver};
+use move_core_types::value::MoveValue;
+use move_core_types::vm_status::{AbortLocation, VMStatus};
+use move_package::BuildConfig;
+use move_package::compilation::compiled_package::{CompiledPackage, CompiledUnit};
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_runtime::native_extensions::NativeContextExtensions;
+use net::{make_net, Block, NetView};
+
+use crate::balances::Balances;
+use crate::call::model::Call;
+use crate::chain_env::ChainEnv;
+use crate::context::Context;
+use crate::effects::{ChangeKind, ExecutionEffects};
+use crate::errmap::explain_abort_code;
+use crate::gas_profile::{GasProfileFormat, GasProfiler};
+
+/// Name of the file with the resources deleted in the sandbox storage.
+pub(crate) const DELETED_RESOURCES_FILE: &str = "deleted_resources.json";
+
+/// Sandbox execution parameters.
+#[derive(Debug)]
+pub struct ExecutionParams {
+    /// Sandbox storage directory.
+    pub storage_dir: PathBuf,
+    /// Maximum number of gas units to be consumed by execution.
+    pub gas_budget: u64,
+    /// If set, the effects of execution will NOT be committed to disk.
+    pub dry_run: bool,
+    /// Print the events and the write-set in JSON format.
+    pub json: bool,
+    /// If set, the gas profile is printed in the given format.
+    pub gas_profile: Option<GasProfileFormat>,
+    /// If set, the state missing in the sandbox storage is loaded from the chain.
+    pub fork: Option<ForkParams>,
+    /// PONT balances before the execution.
+    pub balances: Balances,
+    /// Block height and timestamp of the execution.
+    pub chain_env: ChainEnv,
+}
+
+/// Chain state to fork.
+#[derive(Debug)]
+pub struct ForkParams {
+    /// Node API url.
+    pub url: Url,
+    /// Block to read the state at. The latest block is used if not set.
+    pub height: Option<Block>,
+}
+
+/// Resources deleted in the sandbox storage.
+/// The storage has no files for them, so the forked state view would load them from the chain again.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct DeletedResources(BTreeSet<(AccountAddress, StructTag)>);
+
+impl DeletedResources {
+    /// Loads the deleted resources stored in the sandbox storage directory.
+    pub fn load(storage_dir: &Path) -> Result<DeletedResources, Error> {
+        let path = storage_dir.join(DELETED_RESOURCES_FILE);
+        if !path.exists() {
+            return Ok(DeletedResources::default());
+        }
+        serde_json::from_str(&fs::read_to_string(&path)?)
+            .map_err(|err| anyhow!("Failed to parse {:?}: {}", path, err))
+    }
+
+    /// Stores the deleted resources in the sandbox storage directory.
+    pub fn save(&self, storage_dir: &Path) -> Result<(), Error> {
+        let path = storage_dir.join(DELETED_RESOURCES_FILE);
+        if self.0.is_empty() && !path.exists() {
+            return Ok(());
+        }
+        fs::create_dir_all(storage_dir)?;
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn contains(&self, address: &AccountAddress, tag: &StructTag) -> bool {
+        self.0.contains(&(*address, tag.clone()))
+    }
+
+    /// Records the resource changes of the execution.
+    pub fn update(&mut self, effects: &ExecutionEffects) {
+        for change in &effects.resources {
+            let key = (change.address, change.tag.clone());
+            match change.kind {
+                ChangeKind::Deleted => {
+                    self.0.insert(key);
+                }
+                ChangeKind::Created | ChangeKind::Modified => {
+                    self.0.remove(&key);
+                }
+            }
+        }
+    }
+}
+
+/// Sandbox state view.
+/// Modules and resources are read from the sandbox storage first and then from the chain, if forked.
+/// Resources deleted in the sandbox storage are not read from the chain.
+pub struct ForkStateView<'a> {
+    local: &'a OnDiskStateView,
+    remote: Option<NetView>,
+    deleted: DeletedResources,
+}
+
+impl<'a> ForkStateView<'a> {
+    pub fn new(
+        local: &'a OnDiskStateView,
+        remote: Option<NetView>,
+        deleted: DeletedResources,
+    ) -> ForkStateView<'a> {
+        ForkStateView {
+            local,
+            remote,
+            deleted,
+        }
+    }
+
+    /// Creates the view of the sandbox storage forked from the chain.
+    pub fn forked(
+        local: &'a OnDiskStateView,
+        storage_dir: &Path,
+        fork: Option<&ForkParams>,
+    ) -> Result<ForkStateView<'a>> {
+        let remote = fork
+            .map(|fork| {
+                Ok(NetView::new(
+                    make_net(fork.url.clone())?,
+                    fork.height.clone(),
+                ))
+            })
+            .transpose()?;
+        Ok(ForkStateView::new(
+            local,
+            remote,
+            DeletedResources::load(storage_dir)?,
+        ))
+    }
+}
+
+impl<'a> ModuleResolver for ForkStateView<'a> {
+    type Error = Error;
+
+    fn get_module(&self, module_id: &ModuleId) -> Result<Option<Vec<u8>>> {
+        match ModuleResolver::get_module(self.local, module_id)? {
+            Some(module) => Ok(Some(module)),
+            None => match &self.remote {
+                Some(remote) => remote.get_module(module_id),
+                None => Ok(None),
+            },
+        }
+    }
+}
+
+impl<'a> ResourceResolver for ForkStateView<'a> {
+    type Error = Error;
+
+    fn get_resource(&self, address: &AccountAddress, tag: &StructTag) -> Result<Option<Vec<u8>>> {
+        match ResourceResolver::get_resource(self.local, address, tag)? {
+            Some(resource) => Ok(Some(resource)),
+            None if self.deleted.contains(address, tag) => Ok(None),
+            None => match &self.remote {
+                Some(remote) => remote.get_resource(address, tag),
+                None => Ok(None),
+            },
+        }
+    }
+}
+
+/// Executes the script or the script function in the sandbox.
+/// Arguments are passed to the VM as is, so they must be BCS-encoded.
+pub fn execute(
+    ctx: &Context,
+    call: &Call,
+    signers: Vec<AccountAddress>,
+    args: Vec<Vec<u8>>,
+    type_args: Vec<TypeTag>,
+    params: &ExecutionParams,
+) -> Result<()> {
+    let move_args = sandbox_move_args(ctx);
+    let package = PackageContext::new(&move_args.package_path, &move_args.build_config)?;
+    let state = package.prepare_state(&params.storage_dir)?;
+    save_root_modules(&state, package.package())?;
+
+    let profiler = params
+        .gas_profile
+        .map(|_| GasProfiler::start())
+        .transpose()?;
+
+    let vm = MoveVM::new(ctx.native_functions.clone())
+        .map_err(|err| anyhow!("Failed to create VM: {:?}", err))?;
+    let mut gas_status = get_gas_status(&ctx.cost_table, Some(params.gas_budget))?;
+    let view = ForkStateView::forked(&state, &params.storage_dir, params.fork.as_ref())?;
+    let mut extensions = NativeContextExtensions::default();
+    extensions.add(params.balances.clone());
+    extensions.add(params.chain_env);
+    let mut session = vm.new_session_with_extensions(&view, extensions);
+
+    let vm_args = signers
+        .iter()
+        .map(|addr| {
+            MoveValue::Signer(*addr)
+                .simple_serialize()
+                .ok_or_else(|| anyhow!("Failed to serialize signer {}", addr))
+        })
+        .chain(args.into_iter().map(Ok))
+        .collect::<Result<Vec<_>>>()?;
+
+    let res = match call {
+        Call::Script { code } => session
+            .execute_script(code.clone(), type_args.clone(), vm_args, &mut gas_status)
+            .map(|_| ()),
+        Call::ScriptFunction {
+            mod_address,
+            mod_name,
+            func_name,
+        } => session
+            .execute_script_function(
+                &ModuleId::new(*mod_address, mod_name.clone()),
+                func_name,
+                type_args.clone(),
+                vm_args,
+                &mut gas_status,
+            )
+            .map(|_| ()),
+    };
+
+    let gas_used = params
+        .gas_budget
+        .saturating_sub(gas_status.remaining_gas().get());
+    let profile = match (profiler, params.gas_profile) {
+        (Some(profiler), Some(format)) => {
+            let script = match call {
+                Call::Script { code } => Some(code.as_slice()),
+                Call::ScriptFunction { .. } => None,
+            };
+            let profile = profiler.finish(&view, script, &ctx.cost_table, gas_used)?;
+            Some((profile, format))
+        }
+        _ => None,
+    };
+
+    if let Err(err) = res {
+        explain_error(ctx, err, &state, &view, &type_args, &signers)?;
+        if let Some((profile, format)) = profile {
+            println!("{}", profile.report(format)?);
+        }
+        return Ok(());
+    }
+
+    let (changeset, events, mut extensions) = session
+        .finish_with_extensions()
+        .map_err(|err| err.into_vm_status())?;
+    let balances = extensions.remove::<Balances>();
+    if ctx.move_args.verbose {
+        explain_execution_effects(&changeset, &events, &state)?;
+    }
+    let effects = ExecutionEffects::new(&view, &changeset, &events)?;
+    let changeset = local_changeset(changeset, &state)?;
+    maybe_commit_effects(!params.dry_run, Some(changeset), events, &state)?;
+    if !params.dry_run {
+        balances.save(&params.storage_dir)?;
+        let mut deleted = DeletedResources::load(&params.storage_dir)?;
+        deleted.update(&effects);
+        deleted.save(&params.storage_dir)?;
+    }
+
+    if params.json {
+        // one document: the effects, the balances and the gas profile
+        let mut output = effects.to_json()?;
+        output["balances"] = balances.to_json();
+        if let Some((profile, _)) = profile {
+            output["gas_profile"] = profile.to_json();
+        }
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        print!("{}", effects.to_text());
+        if !balances.is_empty() {
+            print!("{}", balances.to_text());
+        }
+        if let Some((profile, format)) = profile {
+            println!("{}", profile.report(format)?);
+        }
+    }
+    Ok(())
+}
+
+/// Explains the execution error.
+/// Aborts with the raw error constants of the project modules are explained by their constants.
+/// Failures in the modules loaded from the chain are explained with the forked state view,
+/// the sandbox storage has no bytecode for them.
+fn explain_error(
+    ctx: &Context,
+    err: VMError,
+    state: &OnDiskStateView,
+    view: &ForkStateView,
+    type_args: &[TypeTag],
+    signers: &[AccountAddress],
+) -> Result<()> {
+    match err.clone().into_vm_status() {
+        VMStatus::MoveAbort(AbortLocation::Module(id), code) => {
+            // raw error constants are not explained by the move-cli
+            let raw = ctx
+                .error_descriptions
+                .module_error_maps
+                .get(&id)
+                .map(|errors| errors.contains_key(&code))
+                .unwrap_or_default();
+            if raw {
+                if let Some(explanation) = explain_abort_code(&ctx.error_descriptions, &id, code)
+                {
+                    println!("{}", explanation);
+                    return Ok(());
+                }
+            }
+        }
+        VMStatus::ExecutionFailure {
+            status_code,
+            location: AbortLocation::Module(id),
+            function,
+            code_offset,
+        } if ModuleResolver::get_module(state, &id)?.is_none() => {
+            let module = view
+                .get_module(&id)?
+                .ok_or_else(|| anyhow!("Module {} not found", id))?;
+            let module = CompiledModule::deserialize(&module)
+                .map_err(|err| anyhow!("Failed to deserialize module {}: {:?}", id, err))?;
+            let handle = module
+                .function_def_at(FunctionDefinitionIndex(function))
+                .function;
+            let name = module.identifier_at(module.function_handle_at(handle).name);
+            println!(
+                "Execution failed with {:?} in {}::{} at code offset {}",
+                status_code, id, name, code_offset
+            );
+            return Ok(());
+        }
+        _ => {}
+    }
+    explain_execution_error(
+        &ctx.error_descriptions,
+        err,
+        state,
+        &[],
+        &[],
+        type_args,
+        signers,
+        &[],
+    )
+}
+
+/// Removes the deletions of the resources missing in the sandbox storage from the changeset.
+/// Such resources were loaded from the chain, so there are no files to delete.
+fn local_changeset(changeset: ChangeSet, local: &OnDiskStateView) -> Result<ChangeSet> {
+    let mut local_changeset = ChangeSet::new();
+    for (address, account) in changeset.into_inner() {
+        let (modules, resources) = account.into_inner();
+        for (name, blob) in modules {
+            local_changeset.add_module_op(ModuleId::new(address, name), blob)?;
+        }
+        for (tag, blob) in resources {
+            if blob.is_none() && ResourceResolver::get_resource(local, &address, &tag)?.is_none()
+            {
+                continue;
+            }
+            local_changeset.add_resource_op(address, tag, blob)?;
+        }
+    }
+    Ok(local_changeset)
+}
+
+/// Move cli arguments used to build the package for the sandbox.
+fn sandbox_move_args(ctx: &Context) -> Move {
+    let named_addresses = ctx
+        .address_declarations()
+        .into_iter()
+        .filter_map(|(k, v)| v.map(|v| (k, v)))
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+
+    Move {
+        package_path: ctx.project_root_dir.clone(),
+        verbose: ctx.move_args.verbose,
+        build_config: BuildConfig {
+            dev_mode: true,
+            test_mode: true,
+            generate_docs: false,
+            generate_abis: false,
+            install_dir: None,
+            force_recompilation: false,
+            additional_named_addresses: named_addresses,
+            language_flavor: None,
+        },
+    }
+}
+
+/// Stores the modules of the project in the sandbox storage.
+/// The modules are always overwritten, so the storage contains the latest build.
+fn save_root_modules(state: &OnDiskStateView, package: &CompiledPackage) -> Result<()> {
+    let modules = package
+        .root_compiled_units
+        .iter()
+        .filter_map(|unit| match &unit.unit {
+            CompiledUnit::Module(module) => Some(&module.module),
+            CompiledUnit::Script(_) => None,
+        })
+        .map(|module| {
+            let mut bytes = vec![];
+            module.serialize(&mut bytes)?;
+            Ok((module.self_id(), bytes))
+        })
+        .collect::<Result<Vec<(ModuleId, Vec<u8>)>>>()?;
+    state.save_modules(&modules)
+}
+
+#[cfg(test)]
+mod tests {
+    use move_cli::sandbox::utils::on_disk_state_view::OnDiskStateView;
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::{ModuleId, StructTag, CORE_CODE_ADDRESS};
+    use move_core_types::resolver::{ModuleResolver, ResourceResolver};
+    use net::NetView;
+    use net::test_utils::FakeNet;
+
+    use crate::effects::{ChangeKind, ExecutionEffects, ResourceChange};
+    use super::{DeletedResources, ForkStateView};
+
+    fn tag(name: &str) -> StructTag {
+        StructTag {
+            address: CORE_CODE_ADDRESS,
+            module: Identifier::new("Store").unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![],
+        }
+    }
+
+    #[test]
+    fn test_fork_state_view() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = OnDiskStateView::create(dir.path().join("build"), dir.path().join("storage"))
+            .unwrap();
+        let addr = AccountAddress::from_hex_literal("0x2").unwrap();
+        local.save_resource(addr, tag("Local"), &[1]).unwrap();
+        local.save_resource(addr, tag("Both"), &[1]).unwrap();
+
+        let module_id = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("Store").unwrap());
+        let mut net = FakeNet::default();
+        net.modules.insert(module_id.clone(), vec![0xa1]);
+        for name in ["Both", "Remote", "Deleted"] {
+            net.resources.insert((addr, tag(name)), vec![2]);
+        }
+
+        let mut deleted = DeletedResources::default();
+        deleted.update(&ExecutionEffects {
+            events: vec![],
+            resources: vec![ResourceChange {
+                address: addr,
+                tag: tag("Deleted"),
+                kind: ChangeKind::Deleted,
+            }],
+            modules: vec![],
+        });
+        let view = ForkStateView::new(&local, Some(NetView::new(Box::new(net), None)), deleted);
+
+        let resource = |name: &str| view.get_resource(&addr, &tag(name)).unwrap();
+        assert_eq!(resource("Local"), Some(vec![1]));
+        assert_eq!(resource("Both"), Some(vec![1]));
+        assert_eq!(resource("Remote"), Some(vec![2]));
+        assert_eq!(resource("Deleted"), None);
+        assert_eq!(resource("Missing"), None);
+        assert_eq!(view.get_module(&module_id).unwrap(), Some(vec![0xa1]));
+
+        let local_view = ForkStateView::new(&local, None, DeletedResources::default());
+        assert_eq!(
+            local_view.get_resource(&addr, &tag("Remote")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_deleted_resources() {
+        let dir = tempfile::tempdir().unwrap();
+        let addr = AccountAddress::from_hex_literal("0x2").unwrap();
+        let change = |kind| ExecutionEffects {
+            events: vec![],
+            resources: vec![ResourceChange {
+                address: addr,
+                tag: tag("Store"),
+                kind,
+            }],
+            modules: vec![],
+        };
+
+        let mut deleted = DeletedResources::load(dir.path()).unwrap();
+        deleted.update(&change(ChangeKind::Deleted));
+        assert!(deleted.contains(&addr, &tag("Store")));
+        deleted.save(dir.path()).unwrap();
+        assert_eq!(DeletedResources::load(dir.path()).unwrap(), deleted);
+
+        deleted.update(&change(ChangeKind::Created));
+        assert!(!deleted.contains(&addr, &tag("Store")));
+    }
+}
@@ -0,0 +1,567 @@
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use std::str::FromStr;
+use anyhow::{Error, Result};
+use itertools::Itertools;
+use serde_json::{json, Value};
+
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::binary_views::BinaryIndexedView;
+use move_binary_format::file_format::{
+    CompiledModule, CompiledScript, FunctionHandleIndex, FunctionInstantiationIndex,
+};
+use move_binary_format::file_format_common::instruction_key;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::gas_schedule::CostTable;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
+use move_core_types::resolver::ModuleResolver;
+use move_vm_types::gas_schedule::bytecode_instruction_costs;
+use move_vm_types::natives::function::native_gas;
+
+use crate::natives::native_cost_index;
+
+/// Environment variable that enables the VM trace.
+const MOVE_VM_TRACE: &str = "MOVE_VM_TRACE";
+
+/// Gas profile output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasProfileFormat {
+    /// Tree of function frames and tables of instructions and natives.
+    Text,
+    /// Machine-readable report.
+    Json,
+    /// Folded stacks for flamegraph tools.
+    Folded,
+}
+
+impl FromStr for GasProfileFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "text" => GasProfileFormat::Text,
+            "json" => GasProfileFormat::Json,
+            "folded" => GasProfileFormat::Folded,
+            _ => bail!(
+                "Unknown gas profile format {}. Expected one of: text, json, folded",
+                s
+            ),
+        })
+    }
+}
+
+/// Collects the VM instruction trace of the execution.
+pub struct GasProfiler {
+    trace_path: PathBuf,
+}
+
+impl GasProfiler {
+    /// Takes the VM trace path passed to the process by `run_traced`.
+    pub fn start() -> Result<GasProfiler, Error> {
+        let trace_path = env::var_os(MOVE_VM_TRACE)
+            .map(PathBuf::from)
+            .ok_or_else(|| anyhow!("The VM trace is not enabled"))?;
+        Ok(GasProfiler { trace_path })
+    }
+
+    /// Builds the profile from the collected trace.
+    /// The cost of each instruction and native call is taken from the cost table,
+    /// size-dependent costs are counted at the minimal size.
+    pub fn finish<R: ModuleResolver>(
+        self,
+        resolver: &R,
+        script: Option<&[u8]>,
+        cost_table: &CostTable,
+        gas_used: u64,
+    ) -> Result<GasProfile, Error> {
+        let trace = fs::read_to_string(&self.trace_path).map_err(|err| {
+            anyhow!(
+                "Failed to read the VM trace {:?}: {}. Make sure dove is built with the 'gas-profile' feature",
+                self.trace_path,
+                err
+            )
+        })?;
+        fs::remove_file(&self.trace_path)?;
+
+        let script = script
+            .map(CompiledScript::deserialize)
+            .transpose()
+            .map_err(|err| anyhow!("Failed to deserialize script bytecode: {:?}", err))?;
+        let mut builder = ProfileBuilder {
+            resolver,
+            script: script.as_ref(),
+            cost_table,
+            instruction_costs: instruction_costs(cost_table),
+            modules: Default::default(),
+            profile: GasProfile {
+                gas_used,
+                scaling_factor: cost_table.gas_constants.gas_unit_scaling_factor,
+                ..Default::default()
+            },
+        };
+        builder.build(&trace)?;
+        Ok(builder.profile)
+    }
+}
+
+/// Gas consumption of the execution.
+/// The VM trace has no gas charges, so the per-frame, per-instruction and per-native numbers
+/// are estimated from the cost table: size-dependent costs are counted at the minimal size.
+/// The measured gas not covered by the estimates is reported as unattributed.
+#[derive(Debug, Default)]
+pub struct GasProfile {
+    /// Measured gas in gas units.
+    pub gas_used: u64,
+    /// Number of internal gas units in one gas unit.
+    pub scaling_factor: u64,
+    /// Estimated internal gas per call stack.
+    pub stacks: BTreeMap<Vec<String>, u64>,
+    /// Estimated internal gas per instruction class: (count, gas).
+    pub instructions: BTreeMap<String, (u64, u64)>,
+    /// Estimated internal gas per native: (count, gas).
+    pub natives: BTreeMap<String, (u64, u64)>,
+}
+
+impl GasProfile {
+    /// Returns the report in the given format.
+    pub fn report(&self, format: GasProfileFormat) -> Result<String, Error> {
+        Ok(match format {
+            GasProfileFormat::Text => self.to_text(),
+            GasProfileFormat::Json => serde_json::to_string_pretty(&self.to_json())?,
+            GasProfileFormat::Folded => self.to_folded(),
+        })
+    }
+
+    /// Sum of the estimates in internal gas units.
+    pub fn estimated(&self) -> u64 {
+        self.stacks.values().sum()
+    }
+
+    /// Measured internal gas units not covered by the estimates:
+    /// the size-dependent costs above the minimal size.
+    pub fn unattributed(&self) -> u64 {
+        self.gas_used
+            .saturating_mul(self.scaling_factor)
+            .saturating_sub(self.estimated())
+    }
+
+    fn to_text(&self) -> String {
+        let mut out = format!("Gas used: {}\n", self.gas_used);
+        out.push_str(&format!(
+            "The numbers below are estimates in internal gas units (1 gas unit = {} internal units).\n\
+            Size-dependent instruction and native costs are counted at the minimal size.\n",
+            self.scaling_factor
+        ));
+        out.push_str(&format!(
+            "Estimated: {}, unattributed: {}\n",
+            self.estimated(),
+            self.unattributed()
+        ));
+        out.push_str("Frames (total / self):\n");
+        print_frame(&mut out, &self.frames(), 1);
+
+        out.push_str("Instructions (count / gas):\n");
+        for (name, (count, gas)) in sorted_by_gas(&self.instructions) {
+            out.push_str(&format!("    {}: {} / {}\n", name, count, gas));
+        }
+        if !self.natives.is_empty() {
+            out.push_str("Natives (count / gas):\n");
+            for (name, (count, gas)) in sorted_by_gas(&self.natives) {
+                out.push_str(&format!("    {}: {} / {}\n", name, count, gas));
+            }
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> Value {
+        let table = |items: &BTreeMap<String, (u64, u64)>| {
+            sorted_by_gas(items)
+                .into_iter()
+                .map(|(name, (count, gas))| json!({ "name": name, "count": count, "gas": gas }))
+                .collect::<Vec<_>>()
+        };
+        json!({
+            "gas_used": self.gas_used,
+            "scaling_factor": self.scaling_factor,
+            "estimated": self.estimated(),
+            "unattributed": self.unattributed(),
+            "frames": self.frames().children.iter().map(Frame::to_json).collect::<Vec<_>>(),
+            "instructions": table(&self.instructions),
+            "natives": table(&self.natives),
+        })
+    }
+
+    fn to_folded(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|(stack, gas)| format!("{} {}\n", stack.join(";"), gas))
+            .collect()
+    }
+
+    /// Builds the tree of the call frames.
+    fn frames(&self) -> Frame {
+        let mut root = Frame::default();
+        for (stack, gas) in &self.stacks {
+            let mut frame = &mut root;
+            for name in stack {
+                frame.total += gas;
+                let index = match frame.children.iter().position(|f| &f.name == name) {
+                    Some(index) => index,
+                    None => {
+                        frame.children.push(Frame {
+                            name: name.to_owned(),
+                            ..Default::default()
+                        });
+                        frame.children.len() - 1
+                    }
+                };
+                frame = &mut frame.children[index];
+            }
+            frame.total += gas;
+            frame.own += gas;
+        }
+        root
+    }
+}
+
+/// Call frame with the estimated gas.
+#[derive(Debug, Default)]
+struct Frame {
+    name: String,
+    total: u64,
+    own: u64,
+    children: Vec<Frame>,
+}
+
+impl Frame {
+    fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "total": self.total,
+            "self": self.own,
+            "children": self.children.iter().map(Frame::to_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+fn print_frame(out: &mut String, frame: &Frame, depth: usize) {
+    for child in frame
+        .children
+        .iter()
+        .sorted_by(|a, b| b.total.cmp(&a.total))
+    {
+        out.push_str(&format!(
+            "{}{}: {} / {}\n",
+            "    ".repeat(depth),
+            child.name,
+            child.total,
+            child.own
+        ));
+        print_frame(out, child, depth + 1);
+    }
+}
+
+fn sorted_by_gas(items: &BTreeMap<String, (u64, u64)>) -> Vec<(&String, (u64, u64))> {
+    items
+        .iter()
+        .map(|(name, val)| (name, *val))
+        .sorted_by(|a, b| (b.1).1.cmp(&(a.1).1))
+        .collect()
+}
+
+/// Returns the cost of each instruction class by its name.
+fn instruction_costs(cost_table: &CostTable) -> HashMap<String, u64> {
+    bytecode_instruction_costs()
+        .into_iter()
+        .filter_map(|(instr, _)| {
+            let cost = cost_table
+                .instruction_table
+                .get(instruction_key(&instr) as usize - 1)?;
+            Some((
+                instruction_name(&format!("{:?}", instr)),
+                cost.total().get(),
+            ))
+        })
+        .collect()
+}
+
+/// Returns the instruction class: `LdU64(1)` -> `LdU64`.
+pub(crate) fn instruction_name(instr: &str) -> String {
+    instr
+        .split(|ch| ch == '(' || ch == ' ' || ch == '{')
+        .next()
+        .unwrap_or(instr)
+        .to_owned()
+}
+
+/// Returns the first index in the instruction: `Call(FunctionHandleIndex(2))` -> 2.
+fn instruction_index(instr: &str) -> Option<u16> {
+    instr
+        .split(|ch: char| !ch.is_ascii_digit())
+        .find(|part| !part.is_empty())?
+        .parse()
+        .ok()
+}
+
+/// Parses the frame name: `0x1::Module::function`.
+fn frame_function(name: &str) -> Option<(ModuleId, Identifier)> {
+    let mut parts = name.split("::");
+    let address = parts.next()?;
+    let module = parts.next()?;
+    let function = parts.next()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    let address = if address.starts_with("0x") {
+        AccountAddress::from_hex_literal(address).ok()?
+    } else {
+        AccountAddress::from_hex(address).ok()?
+    };
+    Some((
+        ModuleId::new(address, Identifier::new(module).ok()?),
+        Identifier::new(function).ok()?,
+    ))
+}
+
+/// Returns the function name in the Move syntax.
+fn function_name(module_id: &ModuleId, function: &Identifier) -> String {
+    format!(
+        "0x{}::{}::{}",
+        module_id.address().short_str_lossless(),
+        module_id.name(),
+        function
+    )
+}
+
+struct ProfileBuilder<'a, R: ModuleResolver> {
+    resolver: &'a R,
+    script: Option<&'a CompiledScript>,
+    cost_table: &'a CostTable,
+    instruction_costs: HashMap<String, u64>,
+    modules: HashMap<ModuleId, Option<CompiledModule>>,
+    profile: GasProfile,
+}
+
+/// Called function.
+struct Callee {
+    name: String,
+    native: Option<String>,
+    native_cost: u64,
+}
+
+impl<'a, R: ModuleResolver> ProfileBuilder<'a, R> {
+    fn build(&mut self, trace: &str) -> Result<(), Error> {
+        let mut stack: Vec<String> = vec![];
+        for line in trace.lines() {
+            let mut parts = line.splitn(3, ',');
+            let (frame, instr) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(frame), Some(_pc), Some(instr)) => (frame, instr),
+                _ => continue,
+            };
+
+            // Keep the stack consistent with the traced frame.
+            let frame_name = frame_function(frame)
+                .map(|(module_id, function)| function_name(&module_id, &function))
+                .unwrap_or_else(|| frame.to_owned());
+            if stack.last() != Some(&frame_name) {
+                match stack.iter().rposition(|name| name == &frame_name) {
+                    Some(index) => stack.truncate(index + 1),
+                    None => stack.push(frame_name),
+                }
+            }
+
+            let name = instruction_name(instr);
+            let cost = self
+                .instruction_costs
+                .get(&name)
+                .copied()
+                .unwrap_or_default();
+            let entry = self.profile.instructions.entry(name.clone()).or_default();
+            entry.0 += 1;
+            entry.1 += cost;
+            *self.profile.stacks.entry(stack.clone()).or_default() += cost;
+
+            match name.as_str() {
+                "Call" | "CallGeneric" => {
+                    let callee = self.callee(frame, &name, instr)?;
+                    match callee {
+                        Some(Callee {
+                            name,
+                            native: Some(native),
+                            native_cost,
+                        }) => {
+                            let entry = self.profile.natives.entry(native).or_default();
+                            entry.0 += 1;
+                            entry.1 += native_cost;
+                            let mut native_stack = stack.clone();
+                            native_stack.push(format!("{} [native]", name));
+                            *self.profile.stacks.entry(native_stack).or_default() += native_cost;
+                        }
+                        Some(Callee { name, .. }) => stack.push(name),
+                        None => {}
+                    }
+                }
+                "Ret" => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolves the function called by the instruction of the frame.
+    fn callee(&mut self, frame: &str, name: &str, instr: &str) -> Result<Option<Callee>, Error> {
+        let index = match instruction_index(instr) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+
+        let (module_id, function) = {
+            let view = match frame_function(frame) {
+                Some((module_id, _)) => match self.module(&module_id)? {
+                    Some(module) => BinaryIndexedView::Module(module),
+                    None => return Ok(None),
+                },
+                None => match self.script {
+                    Some(script) => BinaryIndexedView::Script(script),
+                    None => return Ok(None),
+                },
+            };
+            let handle = if name == "Call" {
+                view.function_handle_at(FunctionHandleIndex(index))
+            } else {
+                let inst = view.function_instantiation_at(FunctionInstantiationIndex(index));
+                view.function_handle_at(inst.handle)
+            };
+            let module_handle = view.module_handle_at(handle.module);
+            (
+                ModuleId::new(
+                    *view.address_identifier_at(module_handle.address),
+                    view.identifier_at(module_handle.name).to_owned(),
+                ),
+                view.identifier_at(handle.name).to_owned(),
+            )
+        };
+
+        let is_native = self
+            .module(&module_id)?
+            .and_then(|module| {
+                module.function_defs().iter().find(|def| {
+                    module.identifier_at(module.function_handle_at(def.function).name)
+                        == function.as_ident_str()
+                })
+            })
+            .map(|def| def.is_native())
+            .unwrap_or_default();
+
+        let name = function_name(&module_id, &function);
+        if !is_native {
+            return Ok(Some(Callee {
+                name,
+                native: None,
+                native_cost: 0,
+            }));
+        }
+
+        let index = native_cost_index(module_id.name().as_str(), function.as_str());
+        Ok(Some(Callee {
+            native: Some(match index {
+                Some(index) => format!("{:?}", index),
+                None => name.clone(),
+            }),
+            native_cost: index
+                .map(|index| native_gas(self.cost_table, index, 1).get())
+                .unwrap_or_default(),
+            name,
+        }))
+    }
+
+    fn module(&mut self, id: &ModuleId) -> Result<Option<&CompiledModule>, Error> {
+        if !self.modules.contains_key(id) {
+            let module = self
+                .resolver
+                .get_module(id)
+                .map_err(|err| anyhow!("Failed to load module {}: {:?}", id, err))?
+                .map(|bytes| CompiledModule::deserialize(&bytes))
+                .transpose()
+                .map_err(|err| anyhow!("Failed to deserialize module {}: {:?}", id, err))?;
+            self.modules.insert(id.clone(), module);
+        }
+        Ok(self.modules.get(id).and_then(|module| module.as_ref()))
+    }
+}
+
+/// Runs the command again in a child process with the VM trace written to `trace_path`.
+/// The VM reads the trace path from the environment once, so the path is passed to the child
+/// process instead of changing the environment of the running one.
+/// The child repeats the command line of the dove process and detects itself by `MOVE_VM_TRACE`.
+/// Returns `None` in the child process: it executes the command itself.
+/// The parent process must not build or execute anything after the child.
+pub fn run_traced(trace_path: &Path) -> Result<Option<ExitStatus>, Error> {
+    if env::var_os(MOVE_VM_TRACE).is_some() {
+        return Ok(None);
+    }
+    if trace_path.exists() {
+        fs::remove_file(trace_path)?;
+    }
+    if let Some(parent) = trace_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let status = Command::new(env::current_exe()?)
+        .args(env::args_os().skip(1))
+        .env(MOVE_VM_TRACE, trace_path)
+        .status()?;
+    Ok(Some(status))
+}
+
+/// Returns the path of the VM trace file.
+pub fn trace_path(project_root_dir: &Path) -> PathBuf {
+    project_root_dir.join("build").join("gas_profile.trace")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{frame_function, instruction_index, instruction_name, GasProfile};
+
+    #[test]
+    fn test_parse_trace_line() {
+        assert_eq!(instruction_name("LdU64(10)"), "LdU64");
+        assert_eq!(instruction_name("Add"), "Add");
+        assert_eq!(instruction_name("Call(FunctionHandleIndex(2))"), "Call");
+        assert_eq!(instruction_index("Call(FunctionHandleIndex(2))"), Some(2));
+        assert_eq!(instruction_index("Ret"), None);
+
+        let (module, function) = frame_function("0x1::Vector::length").unwrap();
+        assert_eq!(module.name().as_str(), "Vector");
+        assert_eq!(function.as_str(), "length");
+        frame_function("00000000000000000000000000000001::Vector::length").unwrap();
+        assert!(frame_function("main").is_none());
+    }
+
+    #[test]
+    fn test_frames() {
+        let mut profile = GasProfile::default();
+        profile.stacks.insert(vec!["main".to_owned()], 10);
+        profile
+            .stacks
+            .insert(vec!["main".to_owned(), "0x1::M::f".to_owned()], 5);
+
+        let root = profile.frames();
+        assert_eq!(root.total, 15);
+        assert_eq!(root.children[0].total, 15);
+        assert_eq!(root.children[0].own, 10);
+        assert_eq!(root.children[0].children[0].own, 5);
+        assert_eq!(profile.to_folded(), "main 10\nmain;0x1::M::f 5\n");
+
+        profile.gas_used = 2;
+        profile.scaling_factor = 10;
+        assert_eq!(profile.estimated(), 15);
+        assert_eq!(profile.unattributed(), 5);
+        assert_eq!(profile.to_json()["unattributed"], 5);
+    }
+}
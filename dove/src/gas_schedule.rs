@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use move_binary_format::file_format_common::instruction_key;
+use move_core_types::gas_schedule::{CostTable, GasCost};
+use move_vm_types::gas_schedule::bytecode_instruction_costs;
+
+use crate::gas_profile::instruction_name;
+use crate::natives::{pontem_cost_table, PontNativeCostIndex};
+
+/// Gas schedule overrides.
+/// Instruction costs are keyed by the instruction name (`Add`, `LdU64`, ...),
+/// native costs are keyed by the `PontNativeCostIndex` name (`SHA2_256`, `U256_ADD`, ...).
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct GasSchedule {
+    #[serde(default)]
+    pub instructions: BTreeMap<String, Cost>,
+    #[serde(default)]
+    pub natives: BTreeMap<String, Cost>,
+}
+
+/// Cost of the instruction or the native function.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Cost {
+    pub instruction_gas: u64,
+    pub memory_gas: u64,
+}
+
+impl From<&GasCost> for Cost {
+    fn from(cost: &GasCost) -> Self {
+        Cost {
+            instruction_gas: cost.instruction_gas.get(),
+            memory_gas: cost.memory_gas.get(),
+        }
+    }
+}
+
+impl GasSchedule {
+    /// Loads the gas schedule from a TOML or JSON file.
+    pub fn load(path: &Path) -> Result<GasSchedule, Error> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| anyhow!("Failed to read gas schedule {:?}: {}", path, err))?;
+        let schedule = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(&content).map_err(Error::new),
+            Some("toml") => toml::from_str(&content).map_err(Error::new),
+            _ => bail!(
+                "Unsupported gas schedule format {:?}. Expected .json or .toml file",
+                path
+            ),
+        };
+        schedule.map_err(|err| anyhow!("Failed to parse gas schedule {:?}: {}", path, err))
+    }
+
+    /// Exports the cost table.
+    pub fn from_cost_table(cost_table: &CostTable) -> GasSchedule {
+        let instructions = bytecode_instruction_costs()
+            .into_iter()
+            .filter_map(|(instr, _)| {
+                let cost = cost_table
+                    .instruction_table
+                    .get(instruction_key(&instr) as usize - 1)?;
+                Some((instruction_name(&format!("{:?}", instr)), Cost::from(cost)))
+            })
+            .collect();
+        let natives = PontNativeCostIndex::ALL
+            .iter()
+            .filter_map(|index| {
+                let cost = cost_table.native_table.get(*index as usize)?;
+                Some((index.name(), Cost::from(cost)))
+            })
+            .collect();
+        GasSchedule {
+            instructions,
+            natives,
+        }
+    }
+
+    /// Overrides the costs of the cost table.
+    pub fn apply(&self, cost_table: &mut CostTable) -> Result<(), Error> {
+        let instructions = bytecode_instruction_costs()
+            .into_iter()
+            .map(|(instr, _)| {
+                (
+                    instruction_name(&format!("{:?}", instr)),
+                    instruction_key(&instr) as usize - 1,
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+        for (name, cost) in &self.instructions {
+            let index = instructions
+                .get(name)
+                .ok_or_else(|| anyhow!("Unknown instruction {} in the gas schedule", name))?;
+            let entry = cost_table
+                .instruction_table
+                .get_mut(*index)
+                .ok_or_else(|| anyhow!("Instruction {} is missing in the cost table", name))?;
+            *entry = GasCost::new(cost.instruction_gas, cost.memory_gas);
+        }
+
+        for (name, cost) in &self.natives {
+            let index = PontNativeCostIndex::ALL
+                .iter()
+                .find(|index| &index.name() == name)
+                .ok_or_else(|| {
+                    anyhow!("Unknown native cost index {} in the gas schedule", name)
+                })?;
+            let entry = cost_table
+                .native_table
+                .get_mut(*index as usize)
+                .ok_or_else(|| anyhow!("Native {} is missing in the cost table", name))?;
+            *entry = GasCost::new(cost.instruction_gas, cost.memory_gas);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the pontem cost table overridden by the gas schedule file.
+pub fn load_cost_table(gas_schedule: Option<&Path>) -> Result<CostTable, Error> {
+    let mut cost_table = pontem_cost_table();
+    if let Some(path) = gas_schedule {
+        GasSchedule::load(path)?.apply(&mut cost_table)?;
+    }
+    Ok(cost_table)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::natives::pontem_cost_table;
+    use super::{Cost, GasSchedule};
+
+    #[test]
+    fn test_gas_schedule_roundtrip() {
+        let mut cost_table = pontem_cost_table();
+        let schedule = GasSchedule::from_cost_table(&cost_table);
+        assert!(schedule.instructions.contains_key("Add"));
+        assert!(schedule.natives.contains_key("U256_ADD"));
+
+        let overrides: GasSchedule = toml::from_str(
+            r#"
+            [instructions]
+            Add = { instruction_gas = 7, memory_gas = 2 }
+            [natives]
+            U256_ADD = { instruction_gas = 30, memory_gas = 1 }
+            "#,
+        )
+        .unwrap();
+        overrides.apply(&mut cost_table).unwrap();
+        let schedule = GasSchedule::from_cost_table(&cost_table);
+        assert_eq!(
+            schedule.instructions["Add"],
+            Cost {
+                instruction_gas: 7,
+                memory_gas: 2
+            }
+        );
+        assert_eq!(schedule.natives["U256_ADD"].instruction_gas, 30);
+
+        let unknown: GasSchedule = serde_json::from_str(
+            r#"{"natives": {"U512_ADD": {"instruction_gas": 1, "memory_gas": 1}}}"#,
+        )
+        .unwrap();
+        unknown.apply(&mut cost_table).unwrap_err();
+    }
+}
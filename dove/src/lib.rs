@@ -20,14 +20,28 @@ use std::fs::create_dir;
 use std::path::PathBuf;
 use anyhow::Result;
 
+/// Simulated PONT balances.
+pub mod balances;
 /// Transactions.
 pub mod call;
+/// Simulated block height and timestamp.
+pub mod chain_env;
 /// Dove cli interface.
 pub mod cli;
 /// Dove commands handler.
 pub mod cmd;
 /// Dove execution context.
 pub mod context;
+/// Events and write-set of the local execution.
+pub mod effects;
+/// Project error mapping.
+pub mod errmap;
+/// Local sandbox executor.
+pub mod executor;
+/// Gas profiler of the local execution.
+pub mod gas_profile;
+/// Gas schedule overrides.
+pub mod gas_schedule;
 /// Native functions.
 pub mod natives;
 /// To work with stored access keys
@@ -0,0 +1,85 @@
+use move_binary_format::errors::PartialVMResult;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::{native_gas, NativeResult},
+    pop_arg,
+    values::Value,
+};
+use smallvec::smallvec;
+use std::{collections::VecDeque, vec::Vec};
+use crate::balances::Balances;
+use crate::natives::PontNativeCostIndex;
+
+/// Balances of the session. The session must be created with the `Balances` extension.
+fn balances<'a>(context: &'a mut NativeContext) -> &'a mut Balances {
+    context.extensions_mut().get_mut::<Balances>()
+}
+
+pub fn native_balance(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let addr = pop_arg!(arguments, AccountAddress);
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::PONT_BALANCE, 0);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::u128(balances(context).balance(&addr))],
+    ))
+}
+
+pub fn native_deposit(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let amount = pop_arg!(arguments, u128);
+    let addr = pop_arg!(arguments, AccountAddress);
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::PONT_DEPOSIT, 0);
+    Ok(match balances(context).deposit(addr, amount) {
+        Ok(()) => NativeResult::ok(cost, smallvec![]),
+        Err(code) => NativeResult::err(cost, code),
+    })
+}
+
+pub fn native_withdraw(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let amount = pop_arg!(arguments, u128);
+    let addr = pop_arg!(arguments, AccountAddress);
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::PONT_WITHDRAW, 0);
+    Ok(match balances(context).withdraw(addr, amount) {
+        Ok(()) => NativeResult::ok(cost, smallvec![]),
+        Err(code) => NativeResult::err(cost, code),
+    })
+}
+
+pub fn native_transfer(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let amount = pop_arg!(arguments, u128);
+    let to = pop_arg!(arguments, AccountAddress);
+    let from = pop_arg!(arguments, AccountAddress);
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::PONT_TRANSFER, 0);
+    Ok(match balances(context).transfer(from, to, amount) {
+        Ok(()) => NativeResult::ok(cost, smallvec![]),
+        Err(code) => NativeResult::err(cost, code),
+    })
+}
@@ -0,0 +1,43 @@
+use move_binary_format::errors::PartialVMResult;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::{native_gas, NativeResult},
+    values::Value,
+};
+use smallvec::smallvec;
+use std::{collections::VecDeque, vec::Vec};
+use crate::chain_env::ChainEnv;
+use crate::natives::PontNativeCostIndex;
+
+pub fn block_height(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.is_empty());
+
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::BLOCK_HEIGHT, 0);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::u64(
+            context.extensions().get::<ChainEnv>().block_height
+        )],
+    ))
+}
+
+pub fn timestamp(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.is_empty());
+
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::TIMESTAMP, 0);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::u64(context.extensions().get::<ChainEnv>().timestamp)],
+    ))
+}
@@ -0,0 +1,87 @@
+use move_binary_format::errors::PartialVMResult;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::{native_gas, NativeResult},
+    pop_arg,
+    values::Value,
+};
+use smallvec::smallvec;
+use std::{collections::VecDeque, vec::Vec};
+use tiny_keccak::{Hasher, Keccak};
+use crate::natives::PontNativeCostIndex;
+
+pub fn native_blake2b_256(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let data = pop_arg!(arguments, Vec<u8>);
+    let cost = native_gas(
+        context.cost_table(),
+        PontNativeCostIndex::BLAKE2B_256,
+        data.len(),
+    );
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(blake2b_256(&data))],
+    ))
+}
+
+pub fn native_keccak_256(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let data = pop_arg!(arguments, Vec<u8>);
+    let cost = native_gas(
+        context.cost_table(),
+        PontNativeCostIndex::KECCAK_256,
+        data.len(),
+    );
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(keccak_256(&data))],
+    ))
+}
+
+/// Blake2b with 32 bytes output, as used by Substrate.
+fn blake2b_256(data: &[u8]) -> Vec<u8> {
+    blake2_rfc::blake2b::blake2b(32, &[], data)
+        .as_bytes()
+        .to_vec()
+}
+
+/// Keccak-256, as used by Ethereum.
+fn keccak_256(data: &[u8]) -> Vec<u8> {
+    let mut hasher = Keccak::v256();
+    hasher.update(data);
+    let mut output = [0; 32];
+    hasher.finalize(&mut output);
+    output.to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{blake2b_256, keccak_256};
+
+    #[test]
+    fn test_hashes() {
+        assert_eq!(
+            hex::encode(blake2b_256(b"abc")),
+            "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319"
+        );
+        assert_eq!(
+            hex::encode(keccak_256(b"abc")),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+use anyhow::{Error, Result};
+use better_any::{Tid, TidAble};
+use serde::{Deserialize, Serialize};
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::gas_schedule::InternalGasUnits;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::CORE_CODE_ADDRESS;
+use move_vm_runtime::native_functions::{NativeContext, NativeFunction, NativeFunctionTable};
+use move_vm_types::{
+    loaded_data::runtime_types::Type, natives::function::NativeResult, values::Value,
+};
+use smallvec::SmallVec;
+
+/// Native function mocks of `dove test`.
+/// The mocked natives read the mocks from the native context extensions:
+/// the mock index is the index of the slot.
+///
+/// ```toml
+/// [[mock]]
+/// module = "Signature"
+/// function = "sr25519_verify"
+/// returns = [{ bool = true }]
+///
+/// [[mock]]
+/// address = "0x1"
+/// module = "PontAccount"
+/// function = "withdraw"
+/// abort = 7
+/// ```
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq, Tid)]
+#[serde(deny_unknown_fields)]
+pub struct NativeMocks {
+    #[serde(default, rename = "mock")]
+    pub mocks: Vec<NativeMock>,
+}
+
+/// Replacement of the native function: the scripted return values or the abort code.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct NativeMock {
+    /// Module address. `0x1` by default.
+    pub address: Option<String>,
+    pub module: String,
+    pub function: String,
+    /// Values returned by the mock.
+    #[serde(default)]
+    pub returns: Vec<MockValue>,
+    /// If set, the mock aborts with this code.
+    pub abort: Option<u64>,
+    /// Gas charged by the mock.
+    #[serde(default)]
+    pub cost: u64,
+}
+
+/// Typed value returned by the mock: `{ u64 = 10 }`, `{ vector_u8 = "0x0102" }`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MockValue {
+    Bool(bool),
+    U8(u8),
+    U64(u64),
+    U128(u128),
+    /// Hex address.
+    Address(String),
+    /// Hex encoded bytes.
+    VectorU8(String),
+}
+
+impl MockValue {
+    fn to_value(&self) -> Result<Value, Error> {
+        Ok(match self {
+            MockValue::Bool(val) => Value::bool(*val),
+            MockValue::U8(val) => Value::u8(*val),
+            MockValue::U64(val) => Value::u64(*val),
+            MockValue::U128(val) => Value::u128(*val),
+            MockValue::Address(addr) => Value::address(AccountAddress::from_hex_literal(addr)?),
+            MockValue::VectorU8(bytes) => {
+                Value::vector_u8(hex::decode(bytes.trim_start_matches("0x"))?)
+            }
+        })
+    }
+}
+
+impl NativeMock {
+    fn address(&self) -> Result<AccountAddress, Error> {
+        match &self.address {
+            Some(addr) => AccountAddress::from_hex_literal(addr)
+                .map_err(|_| anyhow!("Invalid mock address {}", addr)),
+            None => Ok(CORE_CODE_ADDRESS),
+        }
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "{}::{}::{}",
+            self.address.as_deref().unwrap_or("0x1"),
+            self.module,
+            self.function
+        )
+    }
+}
+
+impl NativeMocks {
+    /// Loads the mocks from a TOML or JSON file.
+    pub fn load(path: &Path) -> Result<NativeMocks, Error> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| anyhow!("Failed to read native mocks {:?}: {}", path, err))?;
+        let mocks = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(&content).map_err(Error::new),
+            Some("toml") => toml::from_str(&content).map_err(Error::new),
+            _ => bail!(
+                "Unsupported native mocks format {:?}. Expected .json or .toml file",
+                path
+            ),
+        };
+        mocks.map_err(|err| anyhow!("Failed to parse native mocks {:?}: {}", path, err))
+    }
+
+    /// Replaces the natives of the table with the mocks.
+    /// Mocks of the functions missing in the table are added to it.
+    /// The sessions must be created with the mocks in the extensions.
+    pub fn apply(&self, natives: NativeFunctionTable) -> Result<NativeFunctionTable, Error> {
+        ensure!(
+            self.mocks.len() <= MOCK_SLOTS.len(),
+            "Too many native mocks: {}. The maximum is {}",
+            self.mocks.len(),
+            MOCK_SLOTS.len()
+        );
+
+        let mut natives = natives;
+        for (index, mock) in self.mocks.iter().enumerate() {
+            ensure!(
+                mock.abort.is_none() || mock.returns.is_empty(),
+                "Mock {} has both the return values and the abort code",
+                mock.name()
+            );
+            for value in &mock.returns {
+                value.to_value().map_err(|err| {
+                    anyhow!("Invalid return value of mock {}: {}", mock.name(), err)
+                })?;
+            }
+
+            let address = mock.address()?;
+            let module = Identifier::new(mock.module.as_str())?;
+            let function = Identifier::new(mock.function.as_str())?;
+            let slot = MOCK_SLOTS[index];
+            match natives
+                .iter_mut()
+                .find(|(addr, m, f, _)| *addr == address && *m == module && *f == function)
+            {
+                Some(native) => native.3 = slot,
+                None => natives.push((address, module, function, slot)),
+            }
+        }
+
+        Ok(natives)
+    }
+}
+
+fn call_mock(context: &NativeContext, index: usize) -> PartialVMResult<NativeResult> {
+    let mock = &context.extensions().get::<NativeMocks>().mocks[index];
+    let cost = InternalGasUnits::new(mock.cost);
+    match mock.abort {
+        Some(code) => Ok(NativeResult::err(cost, code)),
+        None => {
+            let values = mock
+                .returns
+                .iter()
+                .map(|value| value.to_value().expect("Mock values are checked on load"))
+                .collect::<SmallVec<_>>();
+            Ok(NativeResult::ok(cost, values))
+        }
+    }
+}
+
+macro_rules! mock_slots {
+    ($($name:ident = $index:expr),* $(,)?) => {
+        $(
+            fn $name(
+                context: &mut NativeContext,
+                _ty_args: Vec<Type>,
+                _arguments: VecDeque<Value>,
+            ) -> PartialVMResult<NativeResult> {
+                call_mock(context, $index)
+            }
+        )*
+
+        /// Natives are plain functions, so each mock gets its own function.
+        const MOCK_SLOTS: &[NativeFunction] = &[$($name),*];
+    };
+}
+
+mock_slots!(
+    mock_0 = 0,
+    mock_1 = 1,
+    mock_2 = 2,
+    mock_3 = 3,
+    mock_4 = 4,
+    mock_5 = 5,
+    mock_6 = 6,
+    mock_7 = 7,
+    mock_8 = 8,
+    mock_9 = 9,
+    mock_10 = 10,
+    mock_11 = 11,
+    mock_12 = 12,
+    mock_13 = 13,
+    mock_14 = 14,
+    mock_15 = 15,
+);
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::language_storage::CORE_CODE_ADDRESS;
+    use crate::natives::all_natives;
+    use super::{MockValue, NativeMocks};
+
+    #[test]
+    fn test_apply_mocks() {
+        let mocks: NativeMocks = toml::from_str(
+            r#"
+            [[mock]]
+            module = "Signature"
+            function = "ed25519_verify"
+            returns = [{ bool = true }]
+
+            [[mock]]
+            address = "0x1"
+            module = "Bridge"
+            function = "withdraw"
+            abort = 7
+            "#,
+        )
+        .unwrap();
+        assert_eq!(mocks.mocks[0].returns, vec![MockValue::Bool(true)]);
+
+        let natives = all_natives();
+        let len = natives.len();
+        let natives = mocks.apply(natives).unwrap();
+        assert_eq!(natives.len(), len + 1);
+        assert!(natives.iter().any(|(addr, module, function, _)| {
+            *addr == CORE_CODE_ADDRESS
+                && module.as_str() == "Bridge"
+                && function.as_str() == "withdraw"
+        }));
+
+        let invalid: NativeMocks = toml::from_str(
+            r#"
+            [[mock]]
+            module = "Signature"
+            function = "ed25519_verify"
+            returns = [{ vector_u8 = "0xZZ" }]
+            "#,
+        )
+        .unwrap();
+        invalid.apply(all_natives()).unwrap_err();
+    }
+}
@@ -1,16 +1,30 @@
+use std::collections::VecDeque;
+use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_binary_format::file_format_common::instruction_key;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::gas_schedule::{CostTable, GasCost};
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::CORE_CODE_ADDRESS;
-use move_vm_runtime::native_functions::{NativeFunction, NativeFunctionTable};
+use move_core_types::vm_status::StatusCode;
+use move_vm_runtime::native_functions::{NativeContext, NativeFunction, NativeFunctionTable};
 use move_vm_types::gas_schedule::{bytecode_instruction_costs, new_from_instructions};
+use move_vm_types::loaded_data::runtime_types::Type;
+use move_vm_types::natives::function::NativeResult;
+use move_vm_types::values::Value;
 
 mod account;
+mod balance;
+mod chain;
+mod hash;
+mod mock;
 mod reflect;
 mod signature;
 mod u256;
 
+pub use mock::{MockValue, NativeMock, NativeMocks};
+pub use signature::SUBSTRATE_SIGNING_CONTEXT;
+pub use u256::U256;
+
 #[allow(non_camel_case_types)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 #[repr(u8)]
@@ -44,6 +58,92 @@ pub enum PontNativeCostIndex {
     U256_SUB = 26,
     U256_ADD = 27,
     TYPE_INFO = 28,
+    U256_MOD = 29,
+    U256_POW = 30,
+    U256_SHL = 31,
+    U256_SHR = 32,
+    U256_AND = 33,
+    U256_OR = 34,
+    U256_XOR = 35,
+    U256_COMPARE = 36,
+    U256_SQRT = 37,
+    U256_TO_BYTES = 38,
+    U256_FROM_BYTES = 39,
+    SR25519_VERIFY = 40,
+    SECP256K1_VERIFY = 41,
+    SECP256K1_ECRECOVER = 42,
+    BLAKE2B_256 = 43,
+    KECCAK_256 = 44,
+    TYPE_NAME = 45,
+    BLOCK_HEIGHT = 46,
+    TIMESTAMP = 47,
+    PONT_BALANCE = 48,
+    PONT_DEPOSIT = 49,
+    PONT_WITHDRAW = 50,
+    PONT_TRANSFER = 51,
+}
+
+impl PontNativeCostIndex {
+    /// All cost indexes in the order of the native cost table.
+    pub const ALL: &'static [PontNativeCostIndex] = &[
+        PontNativeCostIndex::SHA2_256,
+        PontNativeCostIndex::SHA3_256,
+        PontNativeCostIndex::ED25519_VERIFY,
+        PontNativeCostIndex::ED25519_THRESHOLD_VERIFY,
+        PontNativeCostIndex::BCS_TO_BYTES,
+        PontNativeCostIndex::LENGTH,
+        PontNativeCostIndex::EMPTY,
+        PontNativeCostIndex::BORROW,
+        PontNativeCostIndex::BORROW_MUT,
+        PontNativeCostIndex::PUSH_BACK,
+        PontNativeCostIndex::POP_BACK,
+        PontNativeCostIndex::DESTROY_EMPTY,
+        PontNativeCostIndex::SWAP,
+        PontNativeCostIndex::ED25519_VALIDATE_KEY,
+        PontNativeCostIndex::SIGNER_BORROW,
+        PontNativeCostIndex::CREATE_SIGNER,
+        PontNativeCostIndex::DESTROY_SIGNER,
+        PontNativeCostIndex::EMIT_EVENT,
+        PontNativeCostIndex::U256_FROM_U8,
+        PontNativeCostIndex::U256_FROM_U64,
+        PontNativeCostIndex::U256_FROM_U128,
+        PontNativeCostIndex::U256_AS_U8,
+        PontNativeCostIndex::U256_AS_U64,
+        PontNativeCostIndex::U256_AS_U128,
+        PontNativeCostIndex::U256_MUL,
+        PontNativeCostIndex::U256_DIV,
+        PontNativeCostIndex::U256_SUB,
+        PontNativeCostIndex::U256_ADD,
+        PontNativeCostIndex::TYPE_INFO,
+        PontNativeCostIndex::U256_MOD,
+        PontNativeCostIndex::U256_POW,
+        PontNativeCostIndex::U256_SHL,
+        PontNativeCostIndex::U256_SHR,
+        PontNativeCostIndex::U256_AND,
+        PontNativeCostIndex::U256_OR,
+        PontNativeCostIndex::U256_XOR,
+        PontNativeCostIndex::U256_COMPARE,
+        PontNativeCostIndex::U256_SQRT,
+        PontNativeCostIndex::U256_TO_BYTES,
+        PontNativeCostIndex::U256_FROM_BYTES,
+        PontNativeCostIndex::SR25519_VERIFY,
+        PontNativeCostIndex::SECP256K1_VERIFY,
+        PontNativeCostIndex::SECP256K1_ECRECOVER,
+        PontNativeCostIndex::BLAKE2B_256,
+        PontNativeCostIndex::KECCAK_256,
+        PontNativeCostIndex::TYPE_NAME,
+        PontNativeCostIndex::BLOCK_HEIGHT,
+        PontNativeCostIndex::TIMESTAMP,
+        PontNativeCostIndex::PONT_BALANCE,
+        PontNativeCostIndex::PONT_DEPOSIT,
+        PontNativeCostIndex::PONT_WITHDRAW,
+        PontNativeCostIndex::PONT_TRANSFER,
+    ];
+
+    /// Returns the cost index name.
+    pub fn name(&self) -> String {
+        format!("{:?}", self)
+    }
 }
 
 impl From<PontNativeCostIndex> for u8 {
@@ -52,6 +152,66 @@ impl From<PontNativeCostIndex> for u8 {
     }
 }
 
+/// Returns the cost index of the native function.
+pub fn native_cost_index(module: &str, function: &str) -> Option<PontNativeCostIndex> {
+    use crate::natives::PontNativeCostIndex as N;
+
+    Some(match (module, function) {
+        ("Hash", "sha2_256") => N::SHA2_256,
+        ("Hash", "sha3_256") => N::SHA3_256,
+        ("PontHash", "blake2b_256") => N::BLAKE2B_256,
+        ("PontHash", "keccak_256") => N::KECCAK_256,
+        ("BCS", "to_bytes") => N::BCS_TO_BYTES,
+        ("Vector", "length") => N::LENGTH,
+        ("Vector", "empty") => N::EMPTY,
+        ("Vector", "borrow") => N::BORROW,
+        ("Vector", "borrow_mut") => N::BORROW_MUT,
+        ("Vector", "push_back") => N::PUSH_BACK,
+        ("Vector", "pop_back") => N::POP_BACK,
+        ("Vector", "destroy_empty") => N::DESTROY_EMPTY,
+        ("Vector", "swap") => N::SWAP,
+        ("Signer", "borrow_address") => N::SIGNER_BORROW,
+        ("Event", "write_to_event_store") => N::EMIT_EVENT,
+        ("Signature", "ed25519_validate_pubkey") => N::ED25519_VALIDATE_KEY,
+        ("Signature", "ed25519_verify") => N::ED25519_VERIFY,
+        ("Signature", "sr25519_verify") => N::SR25519_VERIFY,
+        ("Signature", "secp256k1_verify") => N::SECP256K1_VERIFY,
+        ("Signature", "secp256k1_ecrecover") => N::SECP256K1_ECRECOVER,
+        ("PontAccount" | "Account", "create_signer") => N::CREATE_SIGNER,
+        ("PontAccount" | "Account", "destroy_signer") => N::DESTROY_SIGNER,
+        ("PontAccount", "native_balance") => N::PONT_BALANCE,
+        ("PontAccount", "native_deposit") => N::PONT_DEPOSIT,
+        ("PontAccount", "native_withdraw") => N::PONT_WITHDRAW,
+        ("PontAccount", "native_transfer") => N::PONT_TRANSFER,
+        ("U256", "from_u8") => N::U256_FROM_U8,
+        ("U256", "from_u64") => N::U256_FROM_U64,
+        ("U256", "from_u128") => N::U256_FROM_U128,
+        ("U256", "as_u8") => N::U256_AS_U8,
+        ("U256", "as_u64") => N::U256_AS_U64,
+        ("U256", "as_u128") => N::U256_AS_U128,
+        ("U256", "mul") => N::U256_MUL,
+        ("U256", "div") => N::U256_DIV,
+        ("U256", "sub") => N::U256_SUB,
+        ("U256", "add") => N::U256_ADD,
+        ("Reflect", "type_info") => N::TYPE_INFO,
+        ("Reflect", "type_name") => N::TYPE_NAME,
+        ("Block", "get_current_block_height") => N::BLOCK_HEIGHT,
+        ("Timestamp", "now_microseconds") => N::TIMESTAMP,
+        ("U256", "mod") => N::U256_MOD,
+        ("U256", "pow") => N::U256_POW,
+        ("U256", "shl") => N::U256_SHL,
+        ("U256", "shr") => N::U256_SHR,
+        ("U256", "and") => N::U256_AND,
+        ("U256", "or") => N::U256_OR,
+        ("U256", "xor") => N::U256_XOR,
+        ("U256", "compare") => N::U256_COMPARE,
+        ("U256", "sqrt") => N::U256_SQRT,
+        ("U256", "to_bytes") => N::U256_TO_BYTES,
+        ("U256", "from_bytes") => N::U256_FROM_BYTES,
+        _ => return None,
+    })
+}
+
 pub fn pontem_cost_table() -> CostTable {
     let mut instrs = bytecode_instruction_costs();
     // Note that the DiemVM is expecting the table sorted by instruction order.
@@ -89,6 +249,29 @@ pub fn pontem_cost_table() -> CostTable {
         (N::U256_SUB, GasCost::new(10, 1)),
         (N::U256_ADD, GasCost::new(10, 1)),
         (N::TYPE_INFO, GasCost::new(10, 1)),
+        (N::U256_MOD, GasCost::new(10, 1)),
+        (N::U256_POW, GasCost::new(10, 1)),
+        (N::U256_SHL, GasCost::new(10, 1)),
+        (N::U256_SHR, GasCost::new(10, 1)),
+        (N::U256_AND, GasCost::new(10, 1)),
+        (N::U256_OR, GasCost::new(10, 1)),
+        (N::U256_XOR, GasCost::new(10, 1)),
+        (N::U256_COMPARE, GasCost::new(10, 1)),
+        (N::U256_SQRT, GasCost::new(50, 1)),
+        (N::U256_TO_BYTES, GasCost::new(10, 1)),
+        (N::U256_FROM_BYTES, GasCost::new(10, 1)),
+        (N::SR25519_VERIFY, GasCost::new(61, 1)),
+        (N::SECP256K1_VERIFY, GasCost::new(70, 1)),
+        (N::SECP256K1_ECRECOVER, GasCost::new(80, 1)),
+        (N::BLAKE2B_256, GasCost::new(21, 1)),
+        (N::KECCAK_256, GasCost::new(64, 1)),
+        (N::TYPE_NAME, GasCost::new(10, 1)),
+        (N::BLOCK_HEIGHT, GasCost::new(10, 1)),
+        (N::TIMESTAMP, GasCost::new(10, 1)),
+        (N::PONT_BALANCE, GasCost::new(50, 1)),
+        (N::PONT_DEPOSIT, GasCost::new(50, 1)),
+        (N::PONT_WITHDRAW, GasCost::new(50, 1)),
+        (N::PONT_TRANSFER, GasCost::new(50, 1)),
     ];
     native_table.sort_by_key(|cost| cost.0 as u64);
     let raw_native_table = native_table
@@ -106,6 +289,39 @@ pub fn all_natives() -> NativeFunctionTable {
         .collect()
 }
 
+/// Makes the natives that read the native context extensions (the balances ledger and
+/// the chain environment) fail with an error instead of reading the missing extensions.
+/// Used on the move-cli paths that create sessions without the extensions.
+pub fn without_extensions(natives: NativeFunctionTable) -> NativeFunctionTable {
+    const EXTENSION_NATIVES: &[(&str, &str)] = &[
+        ("PontAccount", "native_balance"),
+        ("PontAccount", "native_deposit"),
+        ("PontAccount", "native_withdraw"),
+        ("PontAccount", "native_transfer"),
+        ("Block", "get_current_block_height"),
+        ("Timestamp", "now_microseconds"),
+    ];
+    natives
+        .into_iter()
+        .map(|(addr, module, function, native)| {
+            if EXTENSION_NATIVES.contains(&(module.as_str(), function.as_str())) {
+                (addr, module, function, native_unavailable as NativeFunction)
+            } else {
+                (addr, module, function, native)
+            }
+        })
+        .collect()
+}
+
+fn native_unavailable(
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    _arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    Err(PartialVMError::new(StatusCode::UNREACHABLE)
+        .with_message("The native is available in `dove run` and `dove test` only".to_string()))
+}
+
 pub fn pontem_natives(diem_framework_addr: AccountAddress) -> NativeFunctionTable {
     const NATIVES: &[(&str, &str, NativeFunction)] = &[
         ("U256", "from_u8", u256::from_u8),
@@ -118,6 +334,17 @@ pub fn pontem_natives(diem_framework_addr: AccountAddress) -> NativeFunctionTabl
         ("U256", "sub", u256::sub),
         ("U256", "mul", u256::mul),
         ("U256", "div", u256::div),
+        ("U256", "mod", u256::r#mod),
+        ("U256", "pow", u256::pow),
+        ("U256", "shl", u256::shl),
+        ("U256", "shr", u256::shr),
+        ("U256", "and", u256::and),
+        ("U256", "or", u256::or),
+        ("U256", "xor", u256::xor),
+        ("U256", "compare", u256::compare),
+        ("U256", "sqrt", u256::sqrt),
+        ("U256", "to_bytes", u256::to_bytes),
+        ("U256", "from_bytes", u256::from_bytes),
         (
             "PontAccount",
             "create_signer",
@@ -130,6 +357,10 @@ pub fn pontem_natives(diem_framework_addr: AccountAddress) -> NativeFunctionTabl
             account::native_destroy_signer,
         ),
         ("Account", "destroy_signer", account::native_destroy_signer),
+        ("PontAccount", "native_balance", balance::native_balance),
+        ("PontAccount", "native_deposit", balance::native_deposit),
+        ("PontAccount", "native_withdraw", balance::native_withdraw),
+        ("PontAccount", "native_transfer", balance::native_transfer),
         (
             "Signature",
             "ed25519_validate_pubkey",
@@ -140,7 +371,27 @@ pub fn pontem_natives(diem_framework_addr: AccountAddress) -> NativeFunctionTabl
             "ed25519_verify",
             signature::native_ed25519_signature_verification,
         ),
+        (
+            "Signature",
+            "sr25519_verify",
+            signature::native_sr25519_signature_verification,
+        ),
+        (
+            "Signature",
+            "secp256k1_verify",
+            signature::native_secp256k1_signature_verification,
+        ),
+        (
+            "Signature",
+            "secp256k1_ecrecover",
+            signature::native_secp256k1_ecrecover,
+        ),
+        ("PontHash", "blake2b_256", hash::native_blake2b_256),
+        ("PontHash", "keccak_256", hash::native_keccak_256),
         ("Reflect", "type_info", reflect::type_info),
+        ("Reflect", "type_name", reflect::type_name),
+        ("Block", "get_current_block_height", chain::block_height),
+        ("Timestamp", "now_microseconds", chain::timestamp),
     ];
     NATIVES
         .iter()
@@ -35,3 +35,24 @@ pub fn type_info(
         Ok(NativeResult::err(cost, INVALID_TYPE_PARAM))
     }
 }
+
+/// Returns the canonical name of any type: `u64`, `vector<u8>`, `0x1::Coin::Coin<0x1::PONT::PONT>`.
+pub fn type_name(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(arguments.is_empty());
+
+    let name = context.type_to_type_tag(&ty_args[0])?.to_string();
+    let cost = native_gas(
+        context.cost_table(),
+        PontNativeCostIndex::TYPE_NAME,
+        name.len(),
+    );
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(name.into_bytes())],
+    ))
+}
@@ -72,3 +72,186 @@ pub fn native_ed25519_signature_verification(
         smallvec![Value::bool(verify_result)],
     ))
 }
+
+/// Signing context of the Substrate sr25519 signatures.
+pub const SUBSTRATE_SIGNING_CONTEXT: &[u8] = b"substrate";
+
+pub fn native_sr25519_signature_verification(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let msg = pop_arg!(arguments, Vec<u8>);
+    let pubkey = pop_arg!(arguments, Vec<u8>);
+    let signature = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(
+        context.cost_table(),
+        PontNativeCostIndex::SR25519_VERIFY,
+        msg.len(),
+    );
+
+    let verify_result = sr25519_verify(&signature, &pubkey, &msg);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(verify_result)],
+    ))
+}
+
+pub fn native_secp256k1_signature_verification(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let hash = pop_arg!(arguments, Vec<u8>);
+    let pubkey = pop_arg!(arguments, Vec<u8>);
+    let signature = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(
+        context.cost_table(),
+        PontNativeCostIndex::SECP256K1_VERIFY,
+        hash.len(),
+    );
+
+    let verify_result = secp256k1_verify(&signature, &pubkey, &hash);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(verify_result)],
+    ))
+}
+
+pub fn native_secp256k1_ecrecover(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let signature = pop_arg!(arguments, Vec<u8>);
+    let hash = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(
+        context.cost_table(),
+        PontNativeCostIndex::SECP256K1_ECRECOVER,
+        hash.len(),
+    );
+
+    let result = match secp256k1_ecrecover(&hash, &signature) {
+        Some(pubkey) => smallvec![Value::vector_u8(pubkey), Value::bool(true)],
+        None => smallvec![Value::vector_u8(vec![]), Value::bool(false)],
+    };
+    Ok(NativeResult::ok(cost, result))
+}
+
+/// Verifies the sr25519 signature made in the Substrate signing context.
+fn sr25519_verify(signature: &[u8], pubkey: &[u8], msg: &[u8]) -> bool {
+    let sig = match schnorrkel::Signature::from_bytes(signature) {
+        Ok(sig) => sig,
+        Err(_) => return false,
+    };
+    let pk = match schnorrkel::PublicKey::from_bytes(pubkey) {
+        Ok(pk) => pk,
+        Err(_) => return false,
+    };
+    pk.verify_simple(SUBSTRATE_SIGNING_CONTEXT, msg, &sig)
+        .is_ok()
+}
+
+/// Verifies the 64 bytes `r || s` ECDSA signature of the 32 bytes message hash.
+/// The public key is either compressed (33 bytes) or uncompressed (65 bytes).
+/// Both the low-s and the high-s forms of the signature are accepted: `s` is normalized
+/// before the verification, as libsecp256k1 rejects the high-s signatures.
+fn secp256k1_verify(signature: &[u8], pubkey: &[u8], hash: &[u8]) -> bool {
+    let msg = match libsecp256k1::Message::parse_slice(hash) {
+        Ok(msg) => msg,
+        Err(_) => return false,
+    };
+    let mut sig = match libsecp256k1::Signature::parse_standard_slice(signature) {
+        Ok(sig) => sig,
+        Err(_) => return false,
+    };
+    sig.normalize_s();
+    let pk = match libsecp256k1::PublicKey::parse_slice(pubkey, None) {
+        Ok(pk) => pk,
+        Err(_) => return false,
+    };
+    libsecp256k1::verify(&msg, &sig, &pk)
+}
+
+/// Recovers the public key from the 65 bytes `r || s || v` ECDSA signature of the 32 bytes message hash.
+/// `v` is either 0/1 or 27/28 (Ethereum). Returns the 64 bytes uncompressed public key without the prefix.
+fn secp256k1_ecrecover(hash: &[u8], signature: &[u8]) -> Option<Vec<u8>> {
+    if signature.len() != 65 {
+        return None;
+    }
+    let msg = libsecp256k1::Message::parse_slice(hash).ok()?;
+    let sig = libsecp256k1::Signature::parse_standard_slice(&signature[..64]).ok()?;
+    let v = match signature[64] {
+        v @ 27..=28 => v - 27,
+        v => v,
+    };
+    let recovery_id = libsecp256k1::RecoveryId::parse(v).ok()?;
+    let pk = libsecp256k1::recover(&msg, &sig, &recovery_id).ok()?;
+    Some(pk.serialize()[1..].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{secp256k1_ecrecover, secp256k1_verify, sr25519_verify, SUBSTRATE_SIGNING_CONTEXT};
+
+    #[test]
+    fn test_sr25519_verify() {
+        let keypair = schnorrkel::Keypair::generate_with(rand::rngs::OsRng);
+        let signature = keypair
+            .sign_simple(SUBSTRATE_SIGNING_CONTEXT, b"message")
+            .to_bytes();
+        let pubkey = keypair.public.to_bytes();
+
+        assert!(sr25519_verify(&signature, &pubkey, b"message"));
+        assert!(!sr25519_verify(&signature, &pubkey, b"another message"));
+        assert!(!sr25519_verify(&signature[1..], &pubkey, b"message"));
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        let secret = libsecp256k1::SecretKey::parse(&[7; 32]).unwrap();
+        let pubkey = libsecp256k1::PublicKey::from_secret_key(&secret);
+        let hash = [42; 32];
+        let (signature, recovery_id) =
+            libsecp256k1::sign(&libsecp256k1::Message::parse(&hash), &secret);
+        let signature = signature.serialize();
+
+        assert!(secp256k1_verify(
+            &signature,
+            &pubkey.serialize_compressed(),
+            &hash
+        ));
+        assert!(secp256k1_verify(&signature, &pubkey.serialize(), &hash));
+        assert!(!secp256k1_verify(&signature, &pubkey.serialize(), &[0; 32]));
+
+        // high-s form of the same signature
+        let mut high_s = libsecp256k1::Signature::parse_standard(&signature).unwrap();
+        high_s.s = -high_s.s;
+        assert!(high_s.s.is_high());
+        assert!(secp256k1_verify(
+            &high_s.serialize(),
+            &pubkey.serialize_compressed(),
+            &hash
+        ));
+
+        let mut rsv = signature.to_vec();
+        rsv.push(recovery_id.serialize() + 27);
+        assert_eq!(
+            secp256k1_ecrecover(&hash, &rsv).unwrap(),
+            pubkey.serialize()[1..].to_vec()
+        );
+        assert!(secp256k1_ecrecover(&hash, &rsv[..64]).is_none());
+    }
+}
@@ -1,5 +1,21 @@
 #![allow(clippy::ptr_offset_with_cast, clippy::assign_op_pattern)]
 
+//! U256 natives.
+//!
+//! Arithmetic failures end with the `ARITHMETIC_ERROR` status, as in the Pontem runtime.
+//! The sub-status holds the reason:
+//! - `EOVERFLOW` (1): the result does not fit into 256 bits (`add`, `mul`, `pow`, `shl`) or is negative (`sub`).
+//! - `EDIVISION_BY_ZERO` (2): the divisor of `div` or `mod` is zero.
+//! - `ECAST_OVERFLOW` (3): the value does not fit into the target type (`as_u8`, `as_u64`, `as_u128`).
+//! - `EINVALID_LENGTH` (4): `from_bytes` got more than 32 bytes.
+//!
+//! The sub-statuses are not Move abort codes: tests expect the failures with a plain `#[expected_failure]`.
+//!
+//! `shl` fails with `EOVERFLOW` if a set bit is shifted past the 256th bit, `shr` drops the low bits
+//! (rounds down, as the division by a power of two).
+//! `pow` is charged per bit of the exponent.
+
+use core::cmp::Ordering;
 use core::ops::Div;
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_core_types::vm_status::StatusCode;
@@ -18,6 +34,22 @@ uint::construct_uint! {
     pub struct U256(4);
 }
 
+/// `ARITHMETIC_ERROR` sub-status: the result does not fit into 256 bits or is negative.
+pub const EOVERFLOW: u64 = 1;
+/// `ARITHMETIC_ERROR` sub-status: division by zero.
+pub const EDIVISION_BY_ZERO: u64 = 2;
+/// `ARITHMETIC_ERROR` sub-status: the value does not fit into the target type.
+pub const ECAST_OVERFLOW: u64 = 3;
+/// `ARITHMETIC_ERROR` sub-status: the byte representation is longer than 32 bytes.
+pub const EINVALID_LENGTH: u64 = 4;
+
+/// `compare` result: the values are equal.
+pub const EQUAL: u8 = 0;
+/// `compare` result: the left value is less than the right one.
+pub const LESS_THAN: u8 = 1;
+/// `compare` result: the left value is greater than the right one.
+pub const GREATER_THAN: u8 = 2;
+
 pub fn from_u8(
     context: &mut NativeContext,
     ty_args: Vec<Type>,
@@ -66,8 +98,10 @@ pub fn as_u8(
 
     let u256 = unwrap_u256(pop_arg!(arguments, Struct))?;
     let value = if u256 > U256::from(u8::MAX) {
-        Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
-            .with_message(format!("Cannot cast u256({}) to u8", u256)))
+        Err(arithmetic_error(
+            ECAST_OVERFLOW,
+            format!("Cannot cast u256({}) to u8", u256),
+        ))
     } else {
         Ok(u256.as_u64() as u8)
     }?;
@@ -86,8 +120,10 @@ pub fn as_u64(
 
     let u256 = unwrap_u256(pop_arg!(arguments, Struct))?;
     let value = if u256 > U256::from(u64::MAX) {
-        Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
-            .with_message(format!("Cannot cast u256({}) to u64", u256)))
+        Err(arithmetic_error(
+            ECAST_OVERFLOW,
+            format!("Cannot cast u256({}) to u64", u256),
+        ))
     } else {
         Ok(u256.as_u64())
     }?;
@@ -107,8 +143,10 @@ pub fn as_u128(
     let u256 = unwrap_u256(pop_arg!(arguments, Struct))?;
 
     let value = if u256 > U256::from(u128::MAX) {
-        Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
-            .with_message(format!("Cannot cast u256({}) to u128", u256)))
+        Err(arithmetic_error(
+            ECAST_OVERFLOW,
+            format!("Cannot cast u256({}) to u128", u256),
+        ))
     } else {
         Ok(u256.as_u128())
     }?;
@@ -130,8 +168,10 @@ pub fn mul(
 
     let (res, overflowed) = l.overflowing_mul(r);
     if overflowed {
-        return Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
-            .with_message(format!("Cannot mul {:?} and {:?}", l, r)));
+        return Err(arithmetic_error(
+            EOVERFLOW,
+            format!("Cannot mul {:?} and {:?}", l, r),
+        ));
     }
 
     let cost = native_gas(context.cost_table(), PontNativeCostIndex::U256_MUL, 0);
@@ -150,8 +190,10 @@ pub fn div(
     let l = unwrap_u256(pop_arg!(arguments, Struct))?;
 
     if r == U256::zero() {
-        return Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
-            .with_message(format!("Cannot div {:?} by {:?}", l, r)));
+        return Err(arithmetic_error(
+            EDIVISION_BY_ZERO,
+            format!("Cannot div {:?} by {:?}", l, r),
+        ));
     }
 
     let res = l.div(r);
@@ -172,8 +214,10 @@ pub fn sub(
 
     let (res, overflowed) = l.overflowing_sub(r);
     if overflowed {
-        return Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
-            .with_message(format!("Cannot sub {:?} from {:?}", r, l)));
+        return Err(arithmetic_error(
+            EOVERFLOW,
+            format!("Cannot sub {:?} from {:?}", r, l),
+        ));
     }
 
     let cost = native_gas(context.cost_table(), PontNativeCostIndex::U256_SUB, 0);
@@ -193,14 +237,269 @@ pub fn add(
 
     let (res, overflowed) = l.overflowing_add(r);
     if overflowed {
-        return Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
-            .with_message(format!("Cannot add {:?} and {:?}", l, r)));
+        return Err(arithmetic_error(
+            EOVERFLOW,
+            format!("Cannot add {:?} and {:?}", l, r),
+        ));
     }
 
     let cost = native_gas(context.cost_table(), PontNativeCostIndex::U256_ADD, 0);
     Ok(NativeResult::ok(cost, smallvec![wrap_u256(res)]))
 }
 
+pub fn r#mod(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let r = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let l = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let res = checked_mod(l, r)?;
+
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::U256_MOD, 0);
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(res)]))
+}
+
+pub fn pow(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let exp = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let base = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let res = checked_pow(base, exp).ok_or_else(|| {
+        arithmetic_error(
+            EOVERFLOW,
+            format!("Cannot raise {:?} to the power of {:?}", base, exp),
+        )
+    })?;
+
+    // exponentiation by squaring: one step per bit of the exponent
+    let cost = native_gas(
+        context.cost_table(),
+        PontNativeCostIndex::U256_POW,
+        exp.bits(),
+    );
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(res)]))
+}
+
+pub fn shl(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let shift = pop_arg!(arguments, u8);
+    let value = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let res = checked_shl(value, shift)?;
+
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::U256_SHL, 0);
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(res)]))
+}
+
+pub fn shr(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let shift = pop_arg!(arguments, u8);
+    let value = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::U256_SHR, 0);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![wrap_u256(value >> shift as usize)],
+    ))
+}
+
+pub fn and(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let r = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let l = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::U256_AND, 0);
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(l & r)]))
+}
+
+pub fn or(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let r = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let l = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::U256_OR, 0);
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(l | r)]))
+}
+
+pub fn xor(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let r = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let l = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::U256_XOR, 0);
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(l ^ r)]))
+}
+
+/// Returns `EQUAL`, `LESS_THAN` or `GREATER_THAN`.
+pub fn compare(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let r = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let l = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::U256_COMPARE, 0);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::u8(compare_u256(l, r))],
+    ))
+}
+
+/// Integer square root rounded down.
+pub fn sqrt(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let value = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::U256_SQRT, 0);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![wrap_u256(value.integer_sqrt())],
+    ))
+}
+
+/// Returns 32 bytes in little-endian order.
+pub fn to_bytes(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let value = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let mut bytes = vec![0; 32];
+    value.to_little_endian(&mut bytes);
+
+    let cost = native_gas(context.cost_table(), PontNativeCostIndex::U256_TO_BYTES, 0);
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(bytes)]))
+}
+
+/// Reads up to 32 bytes in little-endian order.
+pub fn from_bytes(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let bytes = pop_arg!(arguments, Vec<u8>);
+    let res = read_u256(&bytes)?;
+
+    let cost = native_gas(
+        context.cost_table(),
+        PontNativeCostIndex::U256_FROM_BYTES,
+        bytes.len(),
+    );
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(res)]))
+}
+
+/// Exponentiation by squaring, `None` on overflow.
+fn checked_pow(base: U256, exp: U256) -> Option<U256> {
+    let (res, overflowed) = base.overflowing_pow(exp);
+    if overflowed {
+        None
+    } else {
+        Some(res)
+    }
+}
+
+fn checked_mod(l: U256, r: U256) -> PartialVMResult<U256> {
+    if r.is_zero() {
+        return Err(arithmetic_error(
+            EDIVISION_BY_ZERO,
+            format!("Cannot mod {:?} by {:?}", l, r),
+        ));
+    }
+    Ok(l % r)
+}
+
+/// Fails with `EOVERFLOW` if a set bit is shifted out.
+fn checked_shl(value: U256, shift: u8) -> PartialVMResult<U256> {
+    if value.leading_zeros() < shift as u32 {
+        return Err(arithmetic_error(
+            EOVERFLOW,
+            format!("Cannot shift {:?} left by {} bits", value, shift),
+        ));
+    }
+    Ok(value << shift as usize)
+}
+
+fn compare_u256(l: U256, r: U256) -> u8 {
+    match l.cmp(&r) {
+        Ordering::Equal => EQUAL,
+        Ordering::Less => LESS_THAN,
+        Ordering::Greater => GREATER_THAN,
+    }
+}
+
+fn read_u256(bytes: &[u8]) -> PartialVMResult<U256> {
+    if bytes.len() > 32 {
+        return Err(arithmetic_error(
+            EINVALID_LENGTH,
+            format!("Cannot read u256 from {} bytes", bytes.len()),
+        ));
+    }
+    Ok(U256::from_little_endian(bytes))
+}
+
+fn arithmetic_error(sub_status: u64, message: String) -> PartialVMError {
+    PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
+        .with_sub_status(sub_status)
+        .with_message(message)
+}
+
 pub fn unwrap_u256(u256: Struct) -> PartialVMResult<U256> {
     u256.unpack()?
         .next()
@@ -226,3 +525,67 @@ fn wrap_u256(val: U256) -> Value {
     val.to_little_endian(&mut bytes);
     Value::struct_(Struct::pack(vec![Value::vector_u8(bytes)]))
 }
+
+#[cfg(test)]
+mod tests {
+    use move_binary_format::errors::{Location, PartialVMResult};
+    use move_core_types::vm_status::StatusCode;
+    use super::{
+        checked_mod, checked_pow, checked_shl, compare_u256, read_u256, U256, EDIVISION_BY_ZERO,
+        EINVALID_LENGTH, EOVERFLOW, EQUAL, GREATER_THAN, LESS_THAN,
+    };
+
+    fn sub_status(res: PartialVMResult<U256>) -> Option<u64> {
+        let err = res.unwrap_err().finish(Location::Undefined);
+        assert_eq!(err.major_status(), StatusCode::ARITHMETIC_ERROR);
+        err.sub_status()
+    }
+
+    #[test]
+    fn test_checked_pow() {
+        assert_eq!(
+            checked_pow(U256::from(10), U256::from(18)),
+            Some(U256::from(1_000_000_000_000_000_000u128))
+        );
+        assert_eq!(
+            checked_pow(U256::from(2), U256::from(255)),
+            Some(U256::one() << 255)
+        );
+        assert_eq!(checked_pow(U256::from(2), U256::from(256)), None);
+        assert_eq!(checked_pow(U256::zero(), U256::zero()), Some(U256::one()));
+    }
+
+    #[test]
+    fn test_checked_mod() {
+        assert_eq!(
+            checked_mod(U256::from(10), U256::from(3)).unwrap(),
+            U256::one()
+        );
+        assert_eq!(
+            sub_status(checked_mod(U256::from(10), U256::zero())),
+            Some(EDIVISION_BY_ZERO)
+        );
+    }
+
+    #[test]
+    fn test_checked_shl() {
+        assert_eq!(checked_shl(U256::one(), 255).unwrap(), U256::one() << 255);
+        assert_eq!(checked_shl(U256::zero(), 255).unwrap(), U256::zero());
+        assert_eq!(sub_status(checked_shl(U256::from(2), 255)), Some(EOVERFLOW));
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(compare_u256(U256::one(), U256::one()), EQUAL);
+        assert_eq!(compare_u256(U256::zero(), U256::one()), LESS_THAN);
+        assert_eq!(compare_u256(U256::MAX, U256::one()), GREATER_THAN);
+        assert_eq!((EQUAL, LESS_THAN, GREATER_THAN), (0, 1, 2));
+    }
+
+    #[test]
+    fn test_read_u256() {
+        assert_eq!(read_u256(&[1, 1]).unwrap(), U256::from(257));
+        assert_eq!(read_u256(&[0xff; 32]).unwrap(), U256::MAX);
+        assert_eq!(sub_status(read_u256(&[0; 33])), Some(EINVALID_LENGTH));
+    }
+}
@@ -1,3 +1,4 @@
+use std::fs;
 use std::path::PathBuf;
 
 use anyhow::Error;
@@ -6,10 +7,14 @@ use anyhow::Result;
 use url::Url;
 
 use pontem_client::PontemClient;
+use crate::call::model::{Batch, Transaction};
 use crate::cmd::key::cli_entering_a_secret_phrase;
 use crate::wallet_key;
 use crate::wallet_key::WalletKey;
 
+/// Default node address.
+const DEFAULT_NODE_URL: &str = "ws://localhost:9944";
+
 #[derive(Parser, Debug)]
 pub struct NodeAccessParams {
     /// Account from whom to publish. Address or test account name or name secret key.
@@ -22,14 +27,9 @@ pub struct NodeAccessParams {
     #[clap(long = "secret", short)]
     secret_phrase: bool,
 
-    /// The url of the substrate node to query
-    #[clap(
-        long = "url",
-        short,
-        parse(try_from_str),
-        default_value = "ws://localhost:9944"
-    )]
-    url_to_node: Url,
+    /// The url of the substrate node to query [default: ws://localhost:9944]
+    #[clap(long = "url", short, parse(try_from_str))]
+    url_to_node: Option<Url>,
 
     /// Limitation of gas consumption per operation
     #[clap(long = "gas", short)]
@@ -40,6 +40,18 @@ impl NodeAccessParams {
     pub fn need_to_publish(&self) -> bool {
         self.account.is_some() || self.secret_phrase
     }
+
+    /// Returns the http url of the node API if the node url is specified explicitly.
+    pub fn api_url(&self) -> Option<Url> {
+        let mut url = self.url_to_node.clone()?;
+        let scheme = match url.scheme() {
+            "ws" => "http",
+            "wss" => "https",
+            _ => return Some(url),
+        };
+        url.set_scheme(scheme).ok()?;
+        Some(url)
+    }
 }
 
 pub struct Publish {
@@ -58,7 +70,11 @@ pub struct Publish {
 
 impl Publish {
     pub fn apply(&self) -> Result<String> {
-        match self.file_type()? {
+        let file_type = self.file_type()?;
+        if let FileType::TX = file_type {
+            ensure_node_version(&Transaction::decode(&fs::read(&self.file_path)?)?)?;
+        }
+        match file_type {
             FileType::Module => match &self.access {
                 AccessType::SecretPhrase(secret) => self.client.tx_mvm_publish_module(
                     self.file_path_as_str()?,
@@ -94,12 +110,57 @@ impl Publish {
                     test_account,
                 ),
             },
+            FileType::Batch => self.execute_batch(),
         }
     }
+
+    /// Executes the batch transactions one by one. Stops at the first failed transaction.
+    /// Returns the hashes of the executed transactions in the batch order.
+    ///
+    /// The batch is not atomic: the transactions executed before the failed one stay on the chain.
+    /// The pontem client has no batch extrinsic (such as `utility.batchAll`) to submit
+    /// the transactions in one call, so there is no atomic mode.
+    fn execute_batch(&self) -> Result<String> {
+        let batch: Batch = bcs::from_bytes(&fs::read(&self.file_path)?)?;
+        for tx in &batch.transactions {
+            ensure_node_version(tx)?;
+        }
+        let tx_dir = tempfile::Builder::new().prefix("dove_batch").tempdir()?;
+
+        let mut hashes = Vec::with_capacity(batch.transactions.len());
+        for (index, tx) in batch.transactions.iter().enumerate() {
+            let tx_path = tx_dir.path().join(format!("{}.mvt", index));
+            fs::write(&tx_path, bcs::to_bytes(tx)?)?;
+            let tx_path = tx_path
+                .to_str()
+                .ok_or_else(|| anyhow!("Error converting path to string"))?;
+
+            let result = match &self.access {
+                AccessType::SecretPhrase(secret) => {
+                    self.client.tx_mvm_execute(tx_path, self.gas_limit, secret)
+                }
+                AccessType::TestAccount(test_account) => {
+                    self.client
+                        .tx_mvm_execute_dev(tx_path, self.gas_limit, test_account)
+                }
+            };
+            match result {
+                Ok(hash) => hashes.push(hash),
+                Err(err) => bail!(
+                    "Transaction {} of {} failed: {}. Executed transactions: [{}]",
+                    index,
+                    batch.transactions.len(),
+                    err,
+                    hashes.join(", ")
+                ),
+            }
+        }
+        Ok(hashes.join(", "))
+    }
 }
 
 /// PublishParamsCmd - Connection parameters
-/// PathBuf - The path to the file to be published (*.mvt, *.mv, *.pac)
+/// PathBuf - The path to the file to be published (*.mvt, *.mvb, *.mv, *.pac)
 impl TryFrom<(&NodeAccessParams, PathBuf)> for Publish {
     type Error = Error;
 
@@ -108,7 +169,10 @@ impl TryFrom<(&NodeAccessParams, PathBuf)> for Publish {
         let gas_limit = params
             .gas_limit
             .ok_or_else(|| anyhow!("Please specify gas limit"))?;
-        let mut url_to_node = params.url_to_node.clone();
+        let mut url_to_node = match &params.url_to_node {
+            Some(url) => url.clone(),
+            None => Url::parse(DEFAULT_NODE_URL)?,
+        };
 
         let access = if params.secret_phrase {
             // Request secret phrases
@@ -153,8 +217,9 @@ impl Publish {
             "pac" => FileType::Bundle,
             "mv" => FileType::Module,
             "mvt" => FileType::TX,
+            "mvb" => FileType::Batch,
             _ => bail!(
-                "pac or mv extension was expected\n{}",
+                "pac, mv, mvt or mvb extension was expected\n{}",
                 self.file_path.display()
             ),
         })
@@ -177,6 +242,18 @@ enum FileType {
     Bundle,
     Module,
     TX,
+    Batch,
+}
+
+/// The node decodes transactions of version 1 only.
+fn ensure_node_version(tx: &Transaction) -> Result<()> {
+    ensure!(
+        tx.version() == 1,
+        "The node supports transaction version 1 only, the transaction has version {}. \
+        Transactions with --chain-id, --expiration-block or --memo can only be run locally with `dove run`.",
+        tx.version()
+    );
+    Ok(())
 }
 
 /// Checking for a key with this name and getting the content
@@ -197,3 +274,37 @@ fn cli_name_to_key(key_name: &str) -> Result<Option<WalletKey>> {
     }
     phrase.map(Some)
 }
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+    use super::NodeAccessParams;
+
+    fn api_url(url: Option<&str>) -> Option<String> {
+        NodeAccessParams {
+            account: None,
+            secret_phrase: false,
+            url_to_node: url.map(|url| Url::parse(url).unwrap()),
+            gas_limit: None,
+        }
+        .api_url()
+        .map(|url| url.to_string())
+    }
+
+    #[test]
+    fn test_api_url() {
+        assert_eq!(
+            api_url(Some("ws://127.0.0.1:9944")).unwrap(),
+            "http://127.0.0.1:9944/"
+        );
+        assert_eq!(
+            api_url(Some("wss://rpc.pontem.network")).unwrap(),
+            "https://rpc.pontem.network/"
+        );
+        assert_eq!(
+            api_url(Some("http://127.0.0.1:9933")).unwrap(),
+            "http://127.0.0.1:9933/"
+        );
+        assert!(api_url(None).is_none());
+    }
+}
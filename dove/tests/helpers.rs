@@ -48,6 +48,42 @@ pub fn dove(args: &[&str], project_path: &Path) -> Result<String> {
     Ok(String::from_utf8(output.stdout)?)
 }
 
+/// Creates the demo project with the given files, runs `dove test` with the arguments
+/// and deletes the project.
+/// The tests of the demo project are removed, only the given tests are run.
+/// The file paths are relative to the project folder.
+/// Returns true if all tests passed and the test output.
+pub fn run_move_tests(
+    project_name: &str,
+    files: &[(&str, &str)],
+    args: &[&str],
+) -> Result<(bool, String)> {
+    let project_folder = new_demo_project(project_name)?;
+    let tests_folder = project_folder.join("tests");
+    remove_dir_all(&tests_folder)?;
+    create_dir(&tests_folder)?;
+    for (path, content) in files {
+        fs::write(project_folder.join(path), content)?;
+    }
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_dove"))
+        .current_dir(&project_folder)
+        .arg("test")
+        .args(args)
+        .output()?;
+    delete_project(&project_folder)?;
+
+    let stdout = String::from_utf8(output.stdout)?;
+    ensure!(
+        stdout.contains("Test result:"),
+        "Command {:?} did not run the tests. \n Error: \n{} Output: \n{}",
+        args,
+        String::from_utf8(output.stderr).unwrap_or_default(),
+        stdout,
+    );
+    Ok((output.status.success(), stdout))
+}
+
 /// Get the project name from "Move.toml"
 pub fn get_project_name_from_toml(project_path: &Path) -> Option<String> {
     let move_toml_path = project_path.join("Move.toml");
@@ -0,0 +1,117 @@
+mod helpers;
+
+use std::fs;
+use helpers::{new_demo_project, dove, delete_project, run_move_tests};
+
+const PONT_ACCOUNT: &str = r#"
+module Std::PontAccount {
+    native public fun native_balance(addr: address): u128;
+    native public fun native_deposit(addr: address, amount: u128);
+    native public fun native_withdraw(addr: address, amount: u128);
+    native public fun native_transfer(from: address, to: address, amount: u128);
+}
+"#;
+
+const PAY_SCRIPT: &str = r#"
+script {
+    use Std::PontAccount;
+
+    fun pay(from: address, to: address, amount: u128) {
+        PontAccount::native_transfer(from, to, amount);
+    }
+}
+"#;
+
+const BALANCE_TESTS: &str = r#"
+#[test_only]
+module Demo::BalanceTests {
+    use Std::PontAccount;
+
+    #[test]
+    fun seeded_balance() {
+        assert!(PontAccount::native_balance(@Demo) == 1000, 1);
+        assert!(PontAccount::native_balance(@0x4) == 5, 2);
+    }
+
+    // both tests change the Demo balance: each test starts with the initial balances
+    #[test]
+    fun deposit_to_demo() {
+        PontAccount::native_deposit(@Demo, 10);
+        assert!(PontAccount::native_balance(@Demo) == 1010, 3);
+    }
+
+    #[test]
+    fun transfer_from_demo() {
+        PontAccount::native_transfer(@Demo, @0x4, 10);
+        assert!(PontAccount::native_balance(@Demo) == 990, 4);
+        assert!(PontAccount::native_balance(@0x4) == 15, 5);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun insufficient_balance() {
+        PontAccount::native_withdraw(@0x5, 1);
+    }
+}
+"#;
+
+fn new_balances_project(project_name: &str) -> std::path::PathBuf {
+    let project_folder = new_demo_project(project_name).unwrap();
+    fs::write(
+        project_folder.join("sources").join("PontAccount.move"),
+        PONT_ACCOUNT,
+    )
+    .unwrap();
+    project_folder
+}
+
+/// $ dove run 'pay(0x2, 0x3, 30)' --balance 0x2=100
+/// $ dove storage balances
+#[test]
+fn test_cmd_dove_run_with_balances() {
+    let project_folder = new_balances_project("project_run_with_balances");
+    fs::write(project_folder.join("scripts").join("pay.move"), PAY_SCRIPT).unwrap();
+
+    let output = dove(
+        &["run", "pay(0x2, 0x3, 30)", "--balance", "0x2=100"],
+        &project_folder,
+    )
+    .unwrap();
+    assert!(output.contains("PONT balances:"));
+    assert!(output.contains("0x2: 70"));
+    assert!(output.contains("0x3: 30"));
+
+    // insufficient balance: the ledger is not changed
+    let output = match dove(&["run", "pay(0x2, 0x3, 80)"], &project_folder) {
+        Ok(output) => output,
+        Err(err) => err.to_string(),
+    };
+    assert!(output.contains("aborted with code 1"));
+    let output = dove(&["storage", "balances"], &project_folder).unwrap();
+    assert!(output.contains("0x2: 70"));
+    assert!(output.contains("0x3: 30"));
+
+    delete_project(&project_folder).unwrap();
+}
+
+/// Move.toml: [balances] Demo = 1000
+/// $ dove test --balance 0x4=5
+#[test]
+fn test_cmd_dove_test_with_balances() {
+    let manifest = format!(
+        "{}\n[balances]\nDemo = 1000\n",
+        fs::read_to_string("resources/for_tests/Move.toml").unwrap()
+    );
+    let (passed, output) = run_move_tests(
+        "project_test_with_balances",
+        &[
+            ("sources/PontAccount.move", PONT_ACCOUNT),
+            ("tests/BalanceTests.move", BALANCE_TESTS),
+            ("Move.toml", &manifest),
+        ],
+        &["--balance", "0x4=5"],
+    )
+    .unwrap();
+    assert!(passed, "{}", output);
+    assert!(output.contains("passed: 4; failed: 0"));
+}
@@ -0,0 +1,30 @@
+mod helpers;
+
+use std::fs;
+use helpers::{new_demo_project, dove, delete_project};
+
+/// $ dove bench-natives --output native_costs.toml
+/// $ dove run 'main()' --gas-schedule native_costs.toml
+#[test]
+fn test_cmd_dove_bench_natives() {
+    let project_name = "project_bench_natives";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    let output = dove(
+        &["bench-natives", "--output", "native_costs.toml"],
+        &project_folder,
+    )
+    .unwrap();
+    assert!(output.contains("U256_ADD"));
+    assert!(!output.contains("Warning: no benchmark"));
+
+    let costs = fs::read_to_string(project_folder.join("native_costs.toml")).unwrap();
+    assert!(costs.contains("[natives.SR25519_VERIFY]"));
+    dove(
+        &["run", "main()", "--gas-schedule", "native_costs.toml"],
+        &project_folder,
+    )
+    .unwrap();
+
+    delete_project(&project_folder).unwrap();
+}
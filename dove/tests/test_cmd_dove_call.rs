@@ -1,5 +1,7 @@
 mod helpers;
 
+use std::path::Path;
+use dove::call::model::Batch;
 use helpers::{new_demo_project, dove, delete_project};
 
 /// $ dove call 'main()'
@@ -62,3 +64,55 @@ fn test_cmd_dove_call_with_type() {
 
     delete_project(&project_folder).unwrap();
 }
+
+/// Returns the number of arguments of each batch transaction in the batch order.
+fn batch_args(path: &Path) -> Vec<usize> {
+    let batch: Batch = bcs::from_bytes(&std::fs::read(path).unwrap()).unwrap();
+    batch
+        .transactions
+        .into_iter()
+        .map(|tx| tx.inner().args.len())
+        .collect()
+}
+
+/// Batch
+/// $ dove call 'main()' --then 'one_param(true)' --then 'two_params(1,1)'
+/// $ dove call @release.calls
+#[test]
+fn test_cmd_dove_call_batch() {
+    let project_name = "project_call_batch";
+    let project_folder = new_demo_project(project_name).unwrap();
+    let tx_dir = project_folder
+        .join("build")
+        .join("for_tests")
+        .join("transaction");
+
+    dove(
+        &[
+            "call",
+            "main()",
+            "--then",
+            "one_param(true)",
+            "--then",
+            "two_params(1,1)",
+        ],
+        &project_folder,
+    )
+    .unwrap();
+    // main(), one_param(true), two_params(1,1)
+    assert_eq!(batch_args(&tx_dir.join("main_batch.mvb")), vec![0, 1, 2]);
+
+    std::fs::write(
+        project_folder.join("release.calls"),
+        "# release\none_param(true)\n\ntwo_params(1,1)\n",
+    )
+    .unwrap();
+    dove(&["call", "@release.calls"], &project_folder).unwrap();
+    // one_param(true), two_params(1,1)
+    assert_eq!(batch_args(&tx_dir.join("one_param_batch.mvb")), vec![1, 2]);
+
+    std::fs::write(project_folder.join("invalid.calls"), "two_params(1)\n").unwrap();
+    dove(&["call", "@invalid.calls"], &project_folder).unwrap_err();
+
+    delete_project(&project_folder).unwrap();
+}
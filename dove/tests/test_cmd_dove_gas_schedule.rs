@@ -0,0 +1,79 @@
+mod helpers;
+
+use std::fs;
+use helpers::{new_demo_project, dove, delete_project};
+
+fn gas_used(output: &str) -> u64 {
+    let json = &output[output.find('{').unwrap()..];
+    let profile: serde_json::Value = serde_json::from_str(json.trim()).unwrap();
+    profile["gas_used"].as_u64().unwrap()
+}
+
+/// $ dove gas-schedule print
+/// $ dove gas-schedule print --json
+/// $ dove run 'main()' --gas-schedule gas_schedule.json
+#[test]
+fn test_cmd_dove_gas_schedule() {
+    let project_name = "project_gas_schedule";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    let output = dove(&["gas-schedule", "print"], &project_folder).unwrap();
+    assert!(output.contains("[instructions.Ret]"));
+    assert!(output.contains("[natives.SHA2_256]"));
+
+    let output = dove(&["gas-schedule", "print", "--json"], &project_folder).unwrap();
+    let mut schedule: serde_json::Value =
+        serde_json::from_str(&output[output.find('{').unwrap()..]).unwrap();
+    assert!(schedule["natives"]["U256_ADD"]["instruction_gas"].is_u64());
+
+    let default_gas =
+        gas_used(&dove(&["run", "main()", "--gas-profile=json"], &project_folder).unwrap());
+
+    schedule["instructions"]["Ret"]["instruction_gas"] = serde_json::json!(1_000_000);
+    fs::write(
+        project_folder.join("gas_schedule.json"),
+        serde_json::to_string(&schedule).unwrap(),
+    )
+    .unwrap();
+    let output = dove(
+        &[
+            "run",
+            "main()",
+            "--gas-profile=json",
+            "--gas-schedule",
+            project_folder.join("gas_schedule.json").to_str().unwrap(),
+        ],
+        &project_folder,
+    )
+    .unwrap();
+    assert!(gas_used(&output) > default_gas);
+
+    fs::write(
+        project_folder.join("gas_schedule.toml"),
+        "[natives.UNKNOWN]\ninstruction_gas = 1\nmemory_gas = 1\n",
+    )
+    .unwrap();
+    assert!(dove(
+        &[
+            "run",
+            "main()",
+            "--gas-schedule",
+            project_folder.join("gas_schedule.toml").to_str().unwrap(),
+        ],
+        &project_folder,
+    )
+    .is_err());
+    // the gas schedule option belongs to `dove run` and `dove test` only
+    assert!(dove(
+        &[
+            "gas-schedule",
+            "print",
+            "--gas-schedule",
+            project_folder.join("gas_schedule.json").to_str().unwrap(),
+        ],
+        &project_folder,
+    )
+    .is_err());
+
+    delete_project(&project_folder).unwrap();
+}
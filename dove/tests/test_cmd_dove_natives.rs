@@ -0,0 +1,363 @@
+mod helpers;
+
+use std::fs;
+use helpers::run_move_tests;
+
+const REPORTED_TESTS: &str = r#"
+#[test_only]
+module Demo::ReportedTests {
+    #[test]
+    fun passes() {}
+
+    #[test]
+    fun fails() {
+        assert!(false, 42);
+    }
+}
+"#;
+
+/// $ dove test
+#[test]
+fn test_cmd_dove_test_reports_failures() {
+    let (passed, output) = run_move_tests(
+        "project_test_reports_failures",
+        &[("tests/ReportedTests.move", REPORTED_TESTS)],
+        &[],
+    )
+    .unwrap();
+    assert!(!passed);
+    assert!(output.contains("passed: 1; failed: 1"), "{}", output);
+    assert!(output.contains("ReportedTests::fails"));
+}
+
+const U256_MODULE: &str = r#"
+module Std::U256 {
+    struct U256 has copy, drop, store { v: vector<u8> }
+
+    native public fun from_u64(v: u64): U256;
+    native public fun as_u64(v: U256): u64;
+    native public fun add(l: U256, r: U256): U256;
+    native public fun sub(l: U256, r: U256): U256;
+    native public fun div(l: U256, r: U256): U256;
+    native public fun mod(l: U256, r: U256): U256;
+    native public fun pow(base: U256, exp: U256): U256;
+    native public fun shl(v: U256, shift: u8): U256;
+    native public fun shr(v: U256, shift: u8): U256;
+    native public fun and(l: U256, r: U256): U256;
+    native public fun or(l: U256, r: U256): U256;
+    native public fun xor(l: U256, r: U256): U256;
+    native public fun compare(l: U256, r: U256): u8;
+    native public fun sqrt(v: U256): U256;
+    native public fun to_bytes(v: U256): vector<u8>;
+    native public fun from_bytes(v: vector<u8>): U256;
+}
+"#;
+
+const U256_TESTS: &str = r#"
+#[test_only]
+module Demo::U256Tests {
+    use Std::U256::{Self, from_u64, as_u64};
+
+    #[test]
+    fun arithmetic() {
+        assert!(as_u64(U256::mod(from_u64(17), from_u64(5))) == 2, 1);
+        assert!(as_u64(U256::pow(from_u64(10), from_u64(18))) == 1000000000000000000, 2);
+        assert!(as_u64(U256::sqrt(from_u64(99))) == 9, 3);
+    }
+
+    #[test]
+    fun bitwise() {
+        assert!(as_u64(U256::shr(U256::shl(from_u64(3), 200), 199)) == 6, 1);
+        assert!(as_u64(U256::and(from_u64(12), from_u64(10))) == 8, 2);
+        assert!(as_u64(U256::or(from_u64(12), from_u64(10))) == 14, 3);
+        assert!(as_u64(U256::xor(from_u64(12), from_u64(10))) == 6, 4);
+    }
+
+    #[test]
+    fun compare_and_bytes() {
+        assert!(U256::compare(from_u64(1), from_u64(1)) == 0, 1);
+        assert!(U256::compare(from_u64(1), from_u64(2)) == 1, 2);
+        assert!(U256::compare(from_u64(2), from_u64(1)) == 2, 3);
+        assert!(as_u64(U256::from_bytes(x"0201")) == 258, 4);
+        assert!(U256::to_bytes(from_u64(258)) == x"0201000000000000000000000000000000000000000000000000000000000000", 5);
+    }
+
+    #[test]
+    #[expected_failure]
+    fun pow_overflow() {
+        U256::pow(from_u64(2), from_u64(256));
+    }
+
+    #[test]
+    #[expected_failure]
+    fun mod_by_zero() {
+        U256::mod(from_u64(1), from_u64(0));
+    }
+}
+"#;
+
+/// $ dove test
+#[test]
+fn test_cmd_dove_u256_natives() {
+    let (passed, output) = run_move_tests(
+        "project_u256_natives",
+        &[
+            ("sources/U256.move", U256_MODULE),
+            ("tests/U256Tests.move", U256_TESTS),
+        ],
+        &[],
+    )
+    .unwrap();
+    assert!(passed, "{}", output);
+}
+
+const HASH_MODULE: &str = r#"
+module Std::PontHash {
+    native public fun blake2b_256(data: vector<u8>): vector<u8>;
+    native public fun keccak_256(data: vector<u8>): vector<u8>;
+}
+"#;
+
+const HASH_TESTS: &str = r#"
+#[test_only]
+module Demo::HashTests {
+    use Std::PontHash;
+
+    #[test]
+    fun hashes() {
+        assert!(PontHash::blake2b_256(b"abc") == x"bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319", 1);
+        assert!(PontHash::keccak_256(b"abc") == x"4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45", 2);
+    }
+}
+"#;
+
+/// $ dove test
+#[test]
+fn test_cmd_dove_hash_natives() {
+    let (passed, output) = run_move_tests(
+        "project_hash_natives",
+        &[
+            ("sources/PontHash.move", HASH_MODULE),
+            ("tests/HashTests.move", HASH_TESTS),
+        ],
+        &[],
+    )
+    .unwrap();
+    assert!(passed, "{}", output);
+}
+
+const REFLECT_MODULE: &str = r#"
+module Std::Reflect {
+    native public fun type_name<T>(): vector<u8>;
+}
+"#;
+
+const REFLECT_TESTS: &str = r#"
+#[test_only]
+module Demo::ReflectTests {
+    use Std::Reflect::type_name;
+
+    struct PONT {}
+    struct Coin<phantom T> {}
+
+    #[test]
+    fun type_names() {
+        assert!(type_name<u64>() == b"u64", 1);
+        assert!(type_name<vector<vector<u8>>>() == b"vector<vector<u8>>", 2);
+        assert!(type_name<address>() == b"address", 3);
+        assert!(type_name<PONT>() == b"0x2::ReflectTests::PONT", 4);
+        assert!(
+            type_name<Coin<PONT>>() == b"0x2::ReflectTests::Coin<0x2::ReflectTests::PONT>",
+            5
+        );
+        assert!(
+            type_name<vector<Coin<bool>>>() == b"vector<0x2::ReflectTests::Coin<bool>>",
+            6
+        );
+    }
+}
+"#;
+
+/// $ dove test
+#[test]
+fn test_cmd_dove_reflect_type_name() {
+    let (passed, output) = run_move_tests(
+        "project_reflect_type_name",
+        &[
+            ("sources/Reflect.move", REFLECT_MODULE),
+            ("tests/ReflectTests.move", REFLECT_TESTS),
+        ],
+        &[],
+    )
+    .unwrap();
+    assert!(passed, "{}", output);
+}
+
+const CHAIN_MODULES: &str = r#"
+module Std::Block {
+    native public fun get_current_block_height(): u64;
+}
+
+module Std::Timestamp {
+    native public fun now_microseconds(): u64;
+}
+"#;
+
+const CHAIN_TESTS: &str = r#"
+#[test_only]
+module Demo::ChainTests {
+    use Std::Block;
+    use Std::Timestamp;
+
+    #[test]
+    fun chain_env() {
+        assert!(Block::get_current_block_height() == 105, 1);
+        assert!(Timestamp::now_microseconds() == 5000500, 2);
+    }
+}
+"#;
+
+/// $ dove test --block-height 100 --timestamp 5000000 --block-time 100 --advance-blocks 5
+#[test]
+fn test_cmd_dove_chain_env_natives() {
+    let (passed, output) = run_move_tests(
+        "project_chain_env_natives",
+        &[
+            ("sources/Chain.move", CHAIN_MODULES),
+            ("tests/ChainTests.move", CHAIN_TESTS),
+        ],
+        &[
+            "--block-height",
+            "100",
+            "--timestamp",
+            "5000000",
+            "--block-time",
+            "100",
+            "--advance-blocks",
+            "5",
+        ],
+    )
+    .unwrap();
+    assert!(passed, "{}", output);
+}
+
+const MOCKED_MODULES: &str = r#"
+module Std::Signature {
+    native public fun sr25519_verify(signature: vector<u8>, public_key: vector<u8>, message: vector<u8>): bool;
+}
+
+module Std::Bridge {
+    native public fun balance(owner: address): u64;
+    native public fun withdraw(owner: address, amount: u64);
+}
+"#;
+
+const MOCKED_TESTS: &str = r#"
+#[test_only]
+module Demo::MockTests {
+    use Std::Signature;
+    use Std::Bridge;
+
+    #[test]
+    fun mocked_values() {
+        assert!(Signature::sr25519_verify(x"", x"", x""), 1);
+        assert!(Bridge::balance(@0x2) == 500, 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 7)]
+    fun mocked_abort() {
+        Bridge::withdraw(@0x2, 10);
+    }
+}
+"#;
+
+const MOCKS: &str = r#"
+[[mock]]
+module = "Signature"
+function = "sr25519_verify"
+returns = [{ bool = true }]
+
+[[mock]]
+module = "Bridge"
+function = "balance"
+returns = [{ u64 = 500 }]
+
+[[mock]]
+module = "Bridge"
+function = "withdraw"
+abort = 7
+"#;
+
+/// $ dove test --mocks mocks.toml
+/// $ dove test
+#[test]
+fn test_cmd_dove_native_mocks() {
+    let files = [
+        ("sources/Mocked.move", MOCKED_MODULES),
+        ("tests/MockTests.move", MOCKED_TESTS),
+        ("mocks.toml", MOCKS),
+    ];
+    let (passed, output) =
+        run_move_tests("project_native_mocks", &files, &["--mocks", "mocks.toml"]).unwrap();
+    assert!(passed, "{}", output);
+
+    // Move.toml: [package] mocks = "mocks.toml"
+    let manifest = fs::read_to_string("resources/for_tests/Move.toml")
+        .unwrap()
+        .replace("[package]\n", "[package]\nmocks = \"mocks.toml\"\n");
+    let (passed, output) = run_move_tests(
+        "project_native_mocks",
+        &[files[0], files[1], files[2], ("Move.toml", &manifest)],
+        &[],
+    )
+    .unwrap();
+    assert!(passed, "{}", output);
+}
+
+const SIGNATURE_MODULE: &str = r#"
+module Std::Signature {
+    native public fun sr25519_verify(signature: vector<u8>, public_key: vector<u8>, message: vector<u8>): bool;
+}
+"#;
+
+/// $ dove test
+#[test]
+fn test_cmd_dove_sr25519_verify() {
+    let keypair = schnorrkel::Keypair::generate_with(rand::rngs::OsRng);
+    let signature = keypair.sign_simple(dove::natives::SUBSTRATE_SIGNING_CONTEXT, b"message");
+    let tests = format!(
+        r#"
+#[test_only]
+module Demo::SignatureTests {{
+    use Std::Signature;
+
+    const SIGNATURE: vector<u8> = x"{}";
+    const PUBLIC_KEY: vector<u8> = x"{}";
+
+    #[test]
+    fun valid_signature() {{
+        assert!(Signature::sr25519_verify(SIGNATURE, PUBLIC_KEY, b"message"), 1);
+    }}
+
+    #[test]
+    fun wrong_message() {{
+        assert!(!Signature::sr25519_verify(SIGNATURE, PUBLIC_KEY, b"another message"), 2);
+    }}
+}}
+"#,
+        hex::encode(signature.to_bytes()),
+        hex::encode(keypair.public.to_bytes())
+    );
+
+    let (passed, output) = run_move_tests(
+        "project_sr25519_verify",
+        &[
+            ("sources/Signature.move", SIGNATURE_MODULE),
+            ("tests/SignatureTests.move", &tests),
+        ],
+        &[],
+    )
+    .unwrap();
+    assert!(passed, "{}", output);
+}
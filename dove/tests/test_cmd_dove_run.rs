@@ -52,6 +52,60 @@ fn test_cmd_dove_run_with_type() {
     delete_project(&project_folder).unwrap();
 }
 
+/// Vector arguments
+/// $ dove run 'vector_params([1, 2], [[1, 2], 0304], [0x1, 0x2])'
+/// $ dove run vector_params --args [1,2] [[1,2],0304] [0x1,0x2]
+#[test]
+fn test_cmd_dove_run_with_vectors() {
+    let project_name = "project_run_with_vectors";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    for call in [
+        vec!["run", "vector_params([1, 2], [[1, 2], 0304], [0x1, 0x2])"],
+        vec![
+            "run",
+            "vector_params",
+            "--args",
+            "[1,2]",
+            "[[1,2],0304]",
+            "[0x1,0x2]",
+        ],
+    ] {
+        dove(&call, &project_folder).unwrap();
+    }
+
+    delete_project(&project_folder).unwrap();
+}
+
+/// $ dove run vector_params --args-file args.json
+/// $ dove run vector_params --args-file args.toml
+#[test]
+fn test_cmd_dove_run_with_args_file() {
+    let project_name = "project_run_with_args_file";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    std::fs::write(
+        project_folder.join("args.json"),
+        r#"{ "args": { "_a": [1, 2], "_b": [[1, 2], "0304"], "_c": ["0x1", "0x2"] } }"#,
+    )
+    .unwrap();
+    std::fs::write(
+        project_folder.join("args.toml"),
+        r#"args = [[1, 2], [[1, 2], "0304"], ["0x1", "0x2"]]"#,
+    )
+    .unwrap();
+
+    for file in ["args.json", "args.toml"] {
+        dove(
+            &["run", "vector_params", "--args-file", file],
+            &project_folder,
+        )
+        .unwrap();
+    }
+
+    delete_project(&project_folder).unwrap();
+}
+
 /// multiple scripts
 /// $ dove run 'script_1(true)'
 /// $ dove run 'script_2(1,1)'
@@ -67,3 +121,215 @@ fn test_cmd_dove_run_multiple() {
 
     delete_project(&project_folder).unwrap();
 }
+
+/// $ dove run 'main()' --gas-profile
+/// $ dove run 'main()' --gas-profile=json --json
+#[test]
+#[cfg(feature = "gas-profile")]
+fn test_cmd_dove_run_with_gas_profile() {
+    let project_name = "project_run_with_gas_profile";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    let output = dove(&["run", "main()", "--gas-profile"], &project_folder).unwrap();
+    // only the traced child process executes the call
+    assert_eq!(output.matches("Gas used:").count(), 1);
+    assert!(output.contains("unattributed:"));
+    assert!(output.contains("Instructions (count / gas):"));
+
+    let output = dove(
+        &["run", "main()", "--gas-profile=json", "--json"],
+        &project_folder,
+    )
+    .unwrap();
+    let output: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+    let profile = &output["gas_profile"];
+    assert!(profile["gas_used"].is_u64());
+    assert!(profile["unattributed"].is_u64());
+    let rows = |name: &str| profile[name].as_array().cloned().unwrap_or_default();
+    assert!(rows("frames")
+        .iter()
+        .any(|frame| frame["total"].as_u64().unwrap_or_default() > 0));
+    assert!(rows("instructions").iter().any(|row| {
+        row["count"].as_u64().unwrap_or_default() > 0
+            && row["gas"].as_u64().unwrap_or_default() > 0
+    }));
+
+    delete_project(&project_folder).unwrap();
+}
+
+/// $ dove run 'main()'
+/// $ dove run 'main()' --json
+#[test]
+fn test_cmd_dove_run_print_effects() {
+    let project_name = "project_run_print_effects";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    let output = dove(&["run", "main()"], &project_folder).unwrap();
+    assert!(output.contains("Events: none"));
+    assert!(output.contains("Resources: no changes"));
+
+    // the build output is not mixed into the document
+    let output = dove(&["run", "main()", "--json"], &project_folder).unwrap();
+    let effects: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+    assert_eq!(effects["events"], serde_json::json!([]));
+    assert_eq!(effects["resources"], serde_json::json!([]));
+
+    delete_project(&project_folder).unwrap();
+}
+
+const EVENT_MODULE: &str = r#"
+module Std::Event {
+    native public fun write_to_event_store<T: drop + store>(guid: vector<u8>, count: u64, msg: T);
+}
+"#;
+
+const STORE_MODULE: &str = r#"
+module Demo::Store {
+    use Std::Event;
+
+    struct Counter has key {
+        value: u64,
+    }
+
+    struct Stored has drop, store {
+        value: u64,
+    }
+
+    public fun store(account: &signer, value: u64) {
+        move_to(account, Counter { value });
+        Event::write_to_event_store(b"Demo::Store", 0, Stored { value });
+    }
+}
+"#;
+
+const STORE_SCRIPT: &str = r#"
+script {
+    use Demo::Store;
+
+    fun store_value(account: signer, value: u64) {
+        Store::store(&account, value);
+    }
+}
+"#;
+
+/// $ dove run 'store_value(0x2, 5)' --json
+/// $ dove run 'store_value(0x3, 7)'
+#[test]
+fn test_cmd_dove_run_print_write_set_and_events() {
+    let project_name = "project_run_print_write_set_and_events";
+    let project_folder = new_demo_project(project_name).unwrap();
+    let sources = project_folder.join("sources");
+    std::fs::write(sources.join("Event.move"), EVENT_MODULE).unwrap();
+    std::fs::write(sources.join("Store.move"), STORE_MODULE).unwrap();
+    std::fs::write(
+        project_folder.join("scripts").join("store_value.move"),
+        STORE_SCRIPT,
+    )
+    .unwrap();
+
+    let output = dove(&["run", "store_value(0x2, 5)", "--json"], &project_folder).unwrap();
+    let effects: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+    let events = effects["events"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert!(events[0]["type"]
+        .as_str()
+        .unwrap()
+        .ends_with("Store::Stored"));
+    assert_eq!(events[0]["sequence_number"], 0);
+    let resources = effects["resources"].as_array().unwrap();
+    assert_eq!(resources.len(), 1);
+    assert!(resources[0]["type"]
+        .as_str()
+        .unwrap()
+        .ends_with("Store::Counter"));
+    assert_eq!(resources[0]["address"], "0x2");
+    assert_eq!(resources[0]["change"], "created");
+
+    let output = dove(&["run", "store_value(0x3, 7)"], &project_folder).unwrap();
+    assert!(!output.contains("Events: none"));
+    assert!(output.contains("Store::Stored"));
+    assert!(output.contains("Store::Counter"));
+
+    delete_project(&project_folder).unwrap();
+}
+
+/// $ dove call 'two_params(1,2)'
+/// $ dove run build/for_tests/transaction/two_params.mvt
+#[test]
+fn test_cmd_dove_run_tx_file() {
+    let project_name = "project_run_tx_file";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    dove(&["call", "two_params(1,2)"], &project_folder).unwrap();
+    let tx_path = "build/for_tests/transaction/two_params.mvt";
+    dove(&["run", tx_path], &project_folder).unwrap();
+    dove(&["run", tx_path, "--signer", "0x1"], &project_folder).unwrap_err();
+
+    delete_project(&project_folder).unwrap();
+}
+
+const CHECK_SIGNER_SCRIPT: &str = r#"
+script {
+    use Std::Signer;
+
+    fun check_signer(account: signer, expected: address) {
+        assert!(Signer::address_of(&account) == expected, 1);
+    }
+}
+"#;
+
+/// $ dove call 'check_signer(0x3)'
+/// $ dove run build/for_tests/transaction/check_signer.mvt --signer 0x3
+/// $ dove run build/for_tests/transaction/check_signer.mvt --signer 0x4
+#[test]
+fn test_cmd_dove_run_tx_file_with_signer() {
+    let project_name = "project_run_tx_file_with_signer";
+    let project_folder = new_demo_project(project_name).unwrap();
+    std::fs::write(
+        project_folder.join("scripts").join("check_signer.move"),
+        CHECK_SIGNER_SCRIPT,
+    )
+    .unwrap();
+
+    dove(&["call", "check_signer(0x3)"], &project_folder).unwrap();
+    let tx_path = "build/for_tests/transaction/check_signer.mvt";
+    // the placeholder must be replaced
+    dove(&["run", tx_path], &project_folder).unwrap_err();
+    let output = dove(&["run", tx_path, "--signer", "0x3"], &project_folder).unwrap();
+    assert!(!output.contains("aborted"));
+    // the script aborts if the signer differs from the expected address
+    let output = match dove(&["run", tx_path, "--signer", "0x4"], &project_folder) {
+        Ok(output) => output,
+        Err(err) => err.to_string(),
+    };
+    assert!(output.contains("aborted with code 1"));
+
+    delete_project(&project_folder).unwrap();
+}
+
+/// $ dove run 'main()' --block-height 10
+/// $ dove run 'main()' --advance-blocks 5
+#[test]
+fn test_cmd_dove_run_chain_env() {
+    let project_name = "project_run_chain_env";
+    let project_folder = new_demo_project(project_name).unwrap();
+    let env_path = project_folder.join("storage").join("chain_env.json");
+
+    dove(&["run", "main()", "--block-height", "10"], &project_folder).unwrap();
+    dove(&["run", "main()", "--advance-blocks", "5"], &project_folder).unwrap();
+    let env: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&env_path).unwrap()).unwrap();
+    assert_eq!(env["block_height"], 15);
+    assert_eq!(env["timestamp"], 30_000_000);
+
+    dove(
+        &["run", "main()", "--advance-blocks", "5", "--dry-run"],
+        &project_folder,
+    )
+    .unwrap();
+    let env: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&env_path).unwrap()).unwrap();
+    assert_eq!(env["block_height"], 15);
+
+    delete_project(&project_folder).unwrap();
+}
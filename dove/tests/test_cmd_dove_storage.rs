@@ -0,0 +1,70 @@
+mod helpers;
+
+use std::fs;
+use helpers::{new_demo_project, dove, delete_project};
+
+/// $ dove run 'main()' --storage scenario
+/// $ dove storage snapshot step_1 --storage scenario
+/// $ dove storage restore step_1 --storage scenario
+/// $ dove clean state --snapshot step_1
+/// $ dove clean
+#[test]
+fn test_cmd_dove_storage_snapshot() {
+    let project_name = "project_storage_snapshot";
+    let project_folder = new_demo_project(project_name).unwrap();
+    let storage = project_folder.join("scenario");
+    let snapshot = project_folder.join("snapshots").join("step_1");
+
+    dove(&["run", "main()", "--storage", "scenario"], &project_folder).unwrap();
+    assert!(storage.exists());
+
+    dove(
+        &["storage", "snapshot", "step_1", "--storage", "scenario"],
+        &project_folder,
+    )
+    .unwrap();
+    assert!(snapshot.exists());
+    dove(
+        &["storage", "snapshot", "step_1", "--storage", "scenario"],
+        &project_folder,
+    )
+    .unwrap_err();
+
+    fs::remove_dir_all(&storage).unwrap();
+    dove(
+        &["storage", "restore", "step_1", "--storage", "scenario"],
+        &project_folder,
+    )
+    .unwrap();
+    assert!(storage.exists());
+
+    // the project and the snapshots are never replaced
+    dove(
+        &["storage", "restore", "step_1", "--storage", "."],
+        &project_folder,
+    )
+    .unwrap_err();
+    dove(
+        &["storage", "restore", "step_1", "--storage", "sources"],
+        &project_folder,
+    )
+    .unwrap_err();
+    assert!(project_folder.join("Move.toml").exists());
+    assert!(project_folder.join("sources").exists());
+
+    dove(&["clean", "state", "--snapshot", "step_1"], &project_folder).unwrap();
+    assert!(!snapshot.exists());
+    assert!(storage.exists());
+
+    // snapshots survive the full cleaning
+    dove(
+        &["storage", "snapshot", "step_1", "--storage", "scenario"],
+        &project_folder,
+    )
+    .unwrap();
+    dove(&["clean"], &project_folder).unwrap();
+    assert!(snapshot.exists());
+    assert!(!project_folder.join("build").exists());
+
+    delete_project(&project_folder).unwrap();
+}
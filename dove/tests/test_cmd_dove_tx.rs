@@ -0,0 +1,76 @@
+mod helpers;
+
+use helpers::{new_demo_project, dove, delete_project};
+
+/// $ dove call 'two_params(1,2)'
+/// $ dove tx inspect build/for_tests/transaction/two_params.mvt
+/// $ dove tx inspect build/for_tests/transaction/two_params.mvt --json
+#[test]
+fn test_cmd_dove_tx_inspect() {
+    let project_name = "project_tx_inspect";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    dove(&["call", "two_params(1,2)"], &project_folder).unwrap();
+    let tx_path = project_folder
+        .join("build")
+        .join("for_tests")
+        .join("transaction")
+        .join("two_params.mvt");
+    let tx_path = tx_path.to_string_lossy();
+
+    let output = dove(&["tx", "inspect", &tx_path], &project_folder).unwrap();
+    assert!(output.contains("Call: script"), "{}", output);
+    assert!(output.contains("= 1\n"), "{}", output);
+    assert!(output.contains("= 2\n"), "{}", output);
+
+    let output = dove(&["tx", "inspect", &tx_path, "--json"], &project_folder).unwrap();
+    let tx: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(tx["version"], 1);
+    assert_eq!(tx["args"][0]["value"], 1);
+    assert_eq!(tx["args"][1]["value"], 2);
+    assert!(tx["call"]["script"]["disassembly"].is_string());
+
+    delete_project(&project_folder).unwrap();
+}
+
+/// $ dove call 'one_param(true)' --chain-id pontem-testnet --expiration-block 100 --memo memo
+/// $ dove tx inspect build/for_tests/transaction/one_param.mvt --json
+#[test]
+fn test_cmd_dove_tx_inspect_v2() {
+    let project_name = "project_tx_inspect_v2";
+    let project_folder = new_demo_project(project_name).unwrap();
+
+    dove(
+        &[
+            "call",
+            "one_param(true)",
+            "--chain-id",
+            "pontem-testnet",
+            "--expiration-block",
+            "100",
+            "--memo",
+            "memo",
+        ],
+        &project_folder,
+    )
+    .unwrap();
+    let tx_path = project_folder
+        .join("build")
+        .join("for_tests")
+        .join("transaction")
+        .join("one_param.mvt");
+
+    let output = dove(
+        &["tx", "inspect", &tx_path.to_string_lossy(), "--json"],
+        &project_folder,
+    )
+    .unwrap();
+    let tx: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(tx["version"], 2);
+    assert_eq!(tx["chain_id"], "pontem-testnet");
+    assert_eq!(tx["expiration_block"], 100);
+    assert_eq!(tx["memo"], "memo");
+    assert_eq!(tx["args"][0]["value"], true);
+
+    delete_project(&project_folder).unwrap();
+}
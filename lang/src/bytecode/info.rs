@@ -1,10 +1,12 @@
+use std::fmt;
 use anyhow::Error;
 use move_binary_format::access::{ModuleAccess, ScriptAccess};
-use move_binary_format::CompiledModule;
+use move_binary_format::binary_views::BinaryIndexedView;
 use move_binary_format::file_format::{
     Ability, AbilitySet, SignatureToken, StructHandleIndex, Visibility,
 };
 use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::CORE_CODE_ADDRESS;
 use crate::bytecode::accessor::{Bytecode, BytecodeRef};
 
 #[derive(Debug)]
@@ -56,7 +58,7 @@ impl BytecodeInfo {
 
     pub fn find_script_function(&self, need_name: &str) -> Option<Script> {
         match &self.bytecode {
-            Bytecode::Script(name, script, module, _) => {
+            Bytecode::Script(name, script, _, _) => {
                 if name != need_name {
                     return None;
                 }
@@ -67,15 +69,17 @@ impl BytecodeInfo {
                     .map(TypeAbilities::from)
                     .collect();
 
+                let view = BinaryIndexedView::Script(script);
                 let parameters = script
                     .signature_at(script.parameters)
                     .0
                     .iter()
-                    .map(|p| make_type(p, module))
+                    .map(|p| make_type(p, &view))
                     .collect();
 
                 Some(Script {
                     name: name.to_string(),
+                    index: 0,
                     parameters,
                     type_parameters,
                     returns: vec![],
@@ -84,18 +88,20 @@ impl BytecodeInfo {
             Bytecode::Module(module, _) => module
                 .function_defs()
                 .iter()
-                .filter(|def| def.visibility == Visibility::Script)
-                .find(|def| {
+                .enumerate()
+                .filter(|(_, def)| def.visibility == Visibility::Script)
+                .find(|(_, def)| {
                     let handle = module.function_handle_at(def.function);
                     module.identifier_at(handle.name).as_str() == need_name
                 })
-                .map(|def| {
+                .map(|(index, def)| {
+                    let view = BinaryIndexedView::Module(module);
                     let handle = module.function_handle_at(def.function);
                     let parameters = module
                         .signature_at(handle.parameters)
                         .0
                         .iter()
-                        .map(|p| make_type(p, module))
+                        .map(|p| make_type(p, &view))
                         .collect();
 
                     let type_parameters = handle
@@ -107,9 +113,10 @@ impl BytecodeInfo {
 
                     Script {
                         name: module.identifier_at(handle.name).to_string(),
+                        index: index as u16,
                         parameters,
                         type_parameters,
-                        returns: return_.iter().map(|st| make_type(st, module)).collect(),
+                        returns: return_.iter().map(|st| make_type(st, &view)).collect(),
                     }
                 }),
         }
@@ -119,6 +126,8 @@ impl BytecodeInfo {
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
 pub struct Script {
     pub name: String,
+    /// Function definition index.
+    pub index: u16,
     pub parameters: Vec<Type>,
     pub type_parameters: Vec<TypeAbilities>,
     pub returns: Vec<Type>,
@@ -130,14 +139,24 @@ impl Script {
     }
 }
 
+/// Parameter type of the script function.
+///
+/// The bytecode of Move 1.7.1 has no u16, u32 and u256 signature tokens:
+/// u16 and u32 parameters can't be declared, u256 is the `0x1::U256::U256` struct.
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
 pub enum Type {
     Bool,
     U8,
     U64,
     U128,
+    /// 0x1::U256::U256
+    U256,
     Address,
     Signer,
+    /// 0x1::string::String
+    String,
+    /// 0x1::ASCII::String
+    AsciiString,
     Vector(Box<Type>),
     Struct(StructDef),
     Reference(Box<Type>),
@@ -145,6 +164,47 @@ pub enum Type {
     TypeParameter(u16),
 }
 
+impl fmt::Display for Type {
+    /// Formats the type in the Move syntax.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Bool => write!(f, "bool"),
+            Type::U8 => write!(f, "u8"),
+            Type::U64 => write!(f, "u64"),
+            Type::U128 => write!(f, "u128"),
+            Type::U256 => write!(f, "0x1::U256::U256"),
+            Type::Address => write!(f, "address"),
+            Type::Signer => write!(f, "signer"),
+            Type::String => write!(f, "0x1::string::String"),
+            Type::AsciiString => write!(f, "0x1::ASCII::String"),
+            Type::Vector(tp) => write!(f, "vector<{}>", tp),
+            Type::Struct(def) => {
+                write!(
+                    f,
+                    "0x{}::{}::{}",
+                    def.address.short_str_lossless(),
+                    def.module_name,
+                    def.name
+                )?;
+                if !def.type_parameters.is_empty() {
+                    write!(f, "<")?;
+                    for (i, tp) in def.type_parameters.iter().enumerate() {
+                        if i > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}", tp)?;
+                    }
+                    write!(f, ">")?;
+                }
+                Ok(())
+            }
+            Type::Reference(tp) => write!(f, "&{}", tp),
+            Type::MutableReference(tp) => write!(f, "&mut {}", tp),
+            Type::TypeParameter(idx) => write!(f, "T{}", idx),
+        }
+    }
+}
+
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
 pub struct StructDef {
     pub address: AccountAddress,
@@ -153,7 +213,28 @@ pub struct StructDef {
     pub type_parameters: Vec<Type>,
 }
 
-fn make_type(tok: &SignatureToken, module: &CompiledModule) -> Type {
+impl StructDef {
+    /// Returns true if the struct is the native `0x1::U256::U256`.
+    pub fn is_u256(&self) -> bool {
+        self.is_core_struct("U256", "U256")
+    }
+
+    /// Returns true if the struct is the utf8 string `0x1::string::String`.
+    pub fn is_utf8_string(&self) -> bool {
+        self.is_core_struct("string", "String")
+    }
+
+    /// Returns true if the struct is the ascii string `0x1::ASCII::String`.
+    pub fn is_ascii_string(&self) -> bool {
+        self.is_core_struct("ASCII", "String")
+    }
+
+    fn is_core_struct(&self, module_name: &str, name: &str) -> bool {
+        self.address == CORE_CODE_ADDRESS && self.module_name == module_name && self.name == name
+    }
+}
+
+fn make_type(tok: &SignatureToken, view: &BinaryIndexedView) -> Type {
     match tok {
         SignatureToken::Bool => Type::Bool,
         SignatureToken::U8 => Type::U8,
@@ -161,32 +242,45 @@ fn make_type(tok: &SignatureToken, module: &CompiledModule) -> Type {
         SignatureToken::U128 => Type::U128,
         SignatureToken::Address => Type::Address,
         SignatureToken::Signer => Type::Signer,
-        SignatureToken::Vector(tp) => Type::Vector(Box::new(make_type(tp, module))),
-        SignatureToken::Struct(idx) => Type::Struct(make_struct_def(*idx, &[], module)),
+        SignatureToken::Vector(tp) => Type::Vector(Box::new(make_type(tp, view))),
+        SignatureToken::Struct(idx) => make_struct_type(make_struct_def(*idx, &[], view)),
         SignatureToken::StructInstantiation(idx, tps) => {
-            Type::Struct(make_struct_def(*idx, tps, module))
+            Type::Struct(make_struct_def(*idx, tps, view))
         }
-        SignatureToken::Reference(rf) => Type::Reference(Box::new(make_type(rf, module))),
+        SignatureToken::Reference(rf) => Type::Reference(Box::new(make_type(rf, view))),
         SignatureToken::MutableReference(tp) => {
-            Type::MutableReference(Box::new(make_type(tp, module)))
+            Type::MutableReference(Box::new(make_type(tp, view)))
         }
         SignatureToken::TypeParameter(val) => Type::TypeParameter(*val),
     }
 }
 
+/// Maps well-known structs to dedicated types.
+fn make_struct_type(def: StructDef) -> Type {
+    if def.is_u256() {
+        Type::U256
+    } else if def.is_utf8_string() {
+        Type::String
+    } else if def.is_ascii_string() {
+        Type::AsciiString
+    } else {
+        Type::Struct(def)
+    }
+}
+
 fn make_struct_def(
     idx: StructHandleIndex,
     tps: &[SignatureToken],
-    module: &CompiledModule,
+    view: &BinaryIndexedView,
 ) -> StructDef {
-    let struct_handle = module.struct_handle_at(idx);
-    let struct_module_handle = module.module_handle_at(struct_handle.module);
+    let struct_handle = view.struct_handle_at(idx);
+    let struct_module_handle = view.module_handle_at(struct_handle.module);
 
     StructDef {
-        address: *module.address_identifier_at(struct_module_handle.address),
-        name: module.identifier_at(struct_handle.name).to_string(),
-        type_parameters: tps.iter().map(|tok| make_type(tok, module)).collect(),
-        module_name: module.identifier_at(struct_module_handle.name).to_string(),
+        address: *view.address_identifier_at(struct_module_handle.address),
+        name: view.identifier_at(struct_handle.name).to_string(),
+        type_parameters: tps.iter().map(|tok| make_type(tok, view)).collect(),
+        module_name: view.identifier_at(struct_module_handle.name).to_string(),
     }
 }
 
@@ -218,3 +312,14 @@ pub enum TypeAbility {
     Store,
     Key,
 }
+
+impl fmt::Display for TypeAbility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TypeAbility::Copy => "copy",
+            TypeAbility::Drop => "drop",
+            TypeAbility::Store => "store",
+            TypeAbility::Key => "key",
+        })
+    }
+}
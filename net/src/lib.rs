@@ -11,6 +11,10 @@ mod dnode;
 mod pont;
 use crate::pont::PontNet;
 
+/// In-memory `Net` for tests.
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
 pub type Block = String;
 
 pub fn make_net<T>(uri: T) -> Result<Box<dyn Net>>
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use anyhow::Result;
+
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{ModuleId, StructTag};
+
+use crate::{Block, BytesForBlock, Net};
+
+/// Chain state in memory.
+#[derive(Default)]
+pub struct FakeNet {
+    pub modules: HashMap<ModuleId, Vec<u8>>,
+    pub resources: HashMap<(AccountAddress, StructTag), Vec<u8>>,
+}
+
+fn bytes(bytes: Option<&Vec<u8>>) -> Result<Option<BytesForBlock>> {
+    Ok(bytes.map(|bytes| BytesForBlock(bytes.clone(), "1".to_string())))
+}
+
+impl Net for FakeNet {
+    fn get_module(
+        &self,
+        module_id: &ModuleId,
+        _: &Option<Block>,
+    ) -> Result<Option<BytesForBlock>> {
+        bytes(self.modules.get(module_id))
+    }
+
+    fn get_resource(
+        &self,
+        address: &AccountAddress,
+        tag: &StructTag,
+        _: &Option<Block>,
+    ) -> Result<Option<BytesForBlock>> {
+        bytes(self.resources.get(&(*address, tag.clone())))
+    }
+
+    fn get_resources(
+        &self,
+        _: &AccountAddress,
+        _: &str,
+        _: &Option<Block>,
+    ) -> Result<Option<BytesForBlock>> {
+        Ok(None)
+    }
+
+    fn get_table_entry(
+        &self,
+        _: &AccountAddress,
+        _: &str,
+        _: &str,
+        _: &str,
+        _: &str,
+        _: &Option<Block>,
+    ) -> Result<Option<BytesForBlock>> {
+        Ok(None)
+    }
+
+    fn get_module_abi(&self, _: &ModuleId, _: &Option<Block>) -> Result<Option<BytesForBlock>> {
+        Ok(None)
+    }
+
+    fn get_module_abis(&self, _: &ModuleId, _: &Option<Block>) -> Result<Option<BytesForBlock>> {
+        Ok(None)
+    }
+
+    fn encode_submission(
+        &self,
+        _: &str,
+        _: &str,
+        _: &str,
+        _: &[&str],
+        _: &[&str],
+        _: &Option<Block>,
+    ) -> Result<Option<BytesForBlock>> {
+        Ok(None)
+    }
+}
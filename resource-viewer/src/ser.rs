@@ -24,6 +24,12 @@ pub struct AnnotatedMoveStructWrapper {
     #[serde(with = "AnnotatedMoveStructExt")]
     pub result: AnnotatedMoveStruct,
 }
+/// Serializable annotated move value.
+#[derive(Serialize)]
+pub struct AnnotatedMoveValueWrapper<'a>(
+    #[serde(with = "annotated_move_value_ref")] pub &'a AnnotatedMoveValue,
+);
+
 #[derive(Serialize)]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 #[serde(remote = "move_resource_viewer::AnnotatedMoveStruct")]
@@ -80,6 +86,18 @@ impl AccountAddressExt {
 #[serde(remote = "Identifier")]
 struct IdentifierExt(#[serde(getter = "Identifier::to_string")] pub String);
 
+mod annotated_move_value_ref {
+    use super::{AnnotatedMoveValue, AnnotatedMoveValueExt};
+    use serde::Serializer;
+
+    pub fn serialize<S>(value: &&AnnotatedMoveValue, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        AnnotatedMoveValueExt::serialize(*value, serializer)
+    }
+}
+
 mod vec_annotated_move_value {
     use super::{AnnotatedMoveValue, AnnotatedMoveValueExt};
     use serde::{Serialize, Serializer};